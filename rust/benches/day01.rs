@@ -0,0 +1,36 @@
+//! The day 1 part 2 Aho-Corasick matcher against the regex it replaced,
+//! on the real input shape and on long lines: `cargo bench --bench day01`.
+
+use aoc2023::day01;
+use aoc2023::gen::{self, Size};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn part2(c: &mut Criterion) {
+    let generator = gen::generator(1).unwrap();
+    let lexicon = day01::DigitLexicon::english();
+    let mut group = c.benchmark_group("day01_part2");
+
+    for (lines, cols) in [(1000, 40), (100, 10_000)] {
+        let input = generator.generate(
+            0,
+            Size {
+                n: lines,
+                cols: Some(cols),
+            },
+        );
+        let input = day01::parse_input(&input).unwrap();
+        let id = format!("{}x{}", lines, cols);
+
+        group.bench_with_input(BenchmarkId::new("aho_corasick", &id), &input, |b, input| {
+            b.iter(|| day01::part2(input).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("regex", &id), &input, |b, input| {
+            b.iter(|| day01::part2_regex(input, &lexicon).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, part2);
+criterion_main!(benches);
@@ -0,0 +1,110 @@
+//! Allocation accounting through a counting global allocator.
+//!
+//! The allocator is only installed with the `count-alloc` feature, e.g.
+//! `cargo run --release --features count-alloc --bin aoc -- run all`.
+//! The counters are process wide, so a measurement only means something when nothing else
+//! runs at the same time. That's why the runner solves days one at a time with the feature on.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+pub const ENABLED: bool = cfg!(feature = "count-alloc");
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Forwards to the system allocator, counting allocations and live bytes on the way.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::freed(layout.size());
+    }
+
+    // A realloc counts as a new allocation of the new size, replacing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::freed(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total bytes allocated, including ones freed again.
+    pub bytes: u64,
+    /// Most bytes live at once, over what was already live when the measurement started.
+    pub peak: usize,
+}
+
+/// Counts the allocations made while running `f`.
+/// Only counts anything with the `count-alloc` feature, and measurements must not overlap.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let value = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (value, stats)
+}
+
+#[cfg(all(test, feature = "count-alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (v, stats) = measure(|| vec![0u8; 4096]);
+        assert_eq!(v.len(), 4096);
+        // Other tests allocate concurrently, so only lower bounds hold.
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 4096);
+    }
+}
@@ -0,0 +1,415 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use anyhow::{bail, Context, Result};
+use aoc2023::alloc;
+use aoc2023::client::{self, Client, Fetched, Guesses, Outcome};
+use aoc2023::day02::Bag;
+use aoc2023::gen::{self, Size};
+use aoc2023::oracle;
+use aoc2023::runner::{self, Day, Options, Phase, PhaseReport, TraceFormat, TraceSink};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve `all` days in parallel, or a single day.
+    Run {
+        /// `all` or a day number.
+        days: String,
+
+        /// Directory with the `dayN.txt` inputs.
+        #[arg(long, default_value = "../input")]
+        input_dir: PathBuf,
+
+        /// Solve a single day on this file instead (e.g. the output of `gen`).
+        #[arg(long, conflicts_with = "input_dir")]
+        input: Option<PathBuf>,
+
+        /// Print the step by step events of the days that support tracing (5, 14, 16, 17).
+        #[arg(long)]
+        trace: bool,
+
+        /// Write the events as JSON lines to this file instead.
+        #[arg(long, conflicts_with = "trace")]
+        trace_json: Option<PathBuf>,
+
+        /// The cubes in the bag for day 2, e.g. `red=12,green=13,blue=14`.
+        #[arg(long)]
+        bag: Option<Bag>,
+    },
+    /// Solve a single day repeatedly and report the time (and allocations) of each phase.
+    Bench {
+        day: u32,
+
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+
+        /// Directory with the `dayN.txt` inputs.
+        #[arg(long, default_value = "../input")]
+        input_dir: PathBuf,
+
+        /// Bench on this file instead (e.g. the output of `gen`).
+        #[arg(long, conflicts_with = "input_dir")]
+        input: Option<PathBuf>,
+    },
+    /// Show the line by line details behind a day's answers (days 1 and 2).
+    Explain {
+        day: u32,
+
+        /// Show every line, not only the interesting ones.
+        #[arg(long)]
+        all: bool,
+
+        /// The cubes in the bag for day 2, e.g. `red=12,green=13,blue=14`.
+        #[arg(long)]
+        bag: Option<Bag>,
+
+        #[arg(long, default_value = "../input")]
+        input_dir: PathBuf,
+
+        /// Explain this file instead.
+        #[arg(long, conflicts_with = "input_dir")]
+        input: Option<PathBuf>,
+    },
+    /// Download puzzle inputs into the input directory.
+    Fetch {
+        #[arg(required = true)]
+        days: Vec<u32>,
+
+        /// Download again even if the input is already there.
+        #[arg(long)]
+        force: bool,
+
+        #[arg(long, default_value = "../input")]
+        input_dir: PathBuf,
+
+        /// File with the session token, if `$AOC_SESSION` is not set.
+        #[arg(long)]
+        session_file: Option<PathBuf>,
+
+        #[arg(long, default_value = client::BASE_URL)]
+        base_url: String,
+    },
+    /// Solve a part and submit the answer, unless earlier guesses already rule it out.
+    Submit {
+        day: u32,
+
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+
+        /// Submit this instead of solving.
+        #[arg(long)]
+        answer: Option<String>,
+
+        #[arg(long, default_value = "../input")]
+        input_dir: PathBuf,
+
+        /// Solve on this file instead.
+        #[arg(long, conflicts_with = "input_dir")]
+        input: Option<PathBuf>,
+
+        /// Where submitted answers are kept, defaults to `guesses.json` in the input directory.
+        #[arg(long)]
+        guesses: Option<PathBuf>,
+
+        /// File with the session token, if `$AOC_SESSION` is not set.
+        #[arg(long)]
+        session_file: Option<PathBuf>,
+
+        #[arg(long, default_value = client::BASE_URL)]
+        base_url: String,
+    },
+    /// Print a synthetic input for a day.
+    Gen {
+        day: u32,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// What this counts depends on the day, see `aoc generators`.
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// Columns for the grid days, defaults to `--size`.
+        #[arg(long)]
+        cols: Option<usize>,
+    },
+    /// List the days that have a generator and what `--size` means for them.
+    Generators,
+    /// Compare a day against its brute-force reference on generated inputs.
+    Check {
+        day: u32,
+
+        /// Number of seeds to try at each size.
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+
+        /// Largest size to try, see `aoc generators`.
+        #[arg(long, default_value_t = 5)]
+        size: usize,
+    },
+}
+
+fn select_days(days: &str) -> Result<Vec<Day>> {
+    if days == "all" {
+        return Ok(runner::DAYS.to_vec());
+    }
+
+    let number = days
+        .parse()
+        .with_context(|| format!("expected `all` or a day number, got `{}`", days))?;
+    match runner::day(number) {
+        Some(day) => Ok(vec![day]),
+        None => bail!("day {} is not solved", number),
+    }
+}
+
+/// Solves `day` and returns the answer to `part`.
+fn solve(day: u32, part: u32, input_path: &Path) -> Result<String> {
+    let day = runner::day(day).with_context(|| format!("day {} is not solved", day))?;
+    let input = std::fs::read_to_string(input_path)
+        .with_context(|| format!("reading {}", input_path.display()))?;
+
+    runner::capture_panics();
+    let report = day.run(&input, None);
+    if let Some(failure) = report.failures().next() {
+        bail!(
+            "day {} {:?}: {}",
+            day.number,
+            failure.phase,
+            failure.result.as_ref().unwrap_err()
+        );
+    }
+
+    let phase = if part == 1 {
+        Phase::Part1
+    } else {
+        Phase::Part2
+    };
+    match report.get(phase) {
+        Some(PhaseReport {
+            result: Ok(answer), ..
+        }) => Ok(answer.clone()),
+        _ => bail!("day {} has no part {}", day.number, part),
+    }
+}
+
+fn run(
+    days: &str,
+    input_dir: PathBuf,
+    input: Option<PathBuf>,
+    trace: Option<TraceSink>,
+    options: Options,
+) -> Result<ExitCode> {
+    let days = select_days(days)?;
+    let single = days.len() == 1;
+    if input.is_some() && !single {
+        bail!("--input can only be used with a single day");
+    }
+
+    let mut jobs = vec![];
+    for day in days {
+        let path = input.clone().unwrap_or_else(|| day.input_path(&input_dir));
+        match std::fs::read_to_string(&path) {
+            Ok(input) => jobs.push((day, input)),
+            // Days without a downloaded input are skipped when running everything.
+            Err(e) if !single && e.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("Skipping day {}: no {}", day.number, path.display());
+            }
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    runner::capture_panics();
+    let start = Instant::now();
+    let reports = runner::run_all(&jobs, trace.as_ref(), &options);
+    let elapsed = start.elapsed();
+
+    if let Some(trace) = trace {
+        trace.finish().context("writing trace")?;
+    }
+
+    print!("{}", runner::format_table(&reports));
+    println!("Total: {:.2?}", elapsed);
+    if alloc::ENABLED {
+        println!();
+        print!("{}", runner::format_alloc_table(&reports));
+    }
+
+    let mut failed = false;
+    for report in &reports {
+        for failure in report.failures() {
+            failed = true;
+            let error = failure.result.as_ref().unwrap_err();
+            eprintln!("Day {} {:?}: {}", report.day, failure.phase, error);
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            days,
+            input_dir,
+            input,
+            trace,
+            trace_json,
+            bag,
+        } => {
+            let trace = match trace_json {
+                Some(path) => {
+                    let file = File::create(&path)
+                        .with_context(|| format!("creating {}", path.display()))?;
+                    Some(TraceSink::new(TraceFormat::Json, BufWriter::new(file)))
+                }
+                None if trace => Some(TraceSink::new(TraceFormat::Text, std::io::stdout())),
+                None => None,
+            };
+            run(&days, input_dir, input, trace, Options { bag })
+        }
+        Command::Bench {
+            day,
+            iterations,
+            input_dir,
+            input,
+        } => {
+            let day = runner::day(day).with_context(|| format!("day {} is not solved", day))?;
+            let path = input.unwrap_or_else(|| day.input_path(&input_dir));
+            let input = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display()))?;
+
+            runner::capture_panics();
+            let reports = runner::bench(day, &input, iterations.max(1));
+            print!("{}", runner::format_bench(&reports));
+            if !alloc::ENABLED {
+                println!("(build with --features count-alloc to count allocations)");
+            }
+
+            let failures = reports.last().into_iter().flat_map(|r| r.failures());
+            let mut failed = false;
+            for failure in failures {
+                failed = true;
+                let error = failure.result.as_ref().unwrap_err();
+                eprintln!("Day {} {:?}: {}", day.number, failure.phase, error);
+            }
+            Ok(if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            })
+        }
+        Command::Explain {
+            day,
+            all,
+            bag,
+            input_dir,
+            input,
+        } => {
+            let path = input.unwrap_or_else(|| input_dir.join(format!("day{}.txt", day)));
+            let input = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            print!("{}", runner::explain(day, &input, all, &Options { bag })?);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Fetch {
+            days,
+            force,
+            input_dir,
+            session_file,
+            base_url,
+        } => {
+            let session = client::session_token(session_file.as_deref())?;
+            let mut client = Client::new(&base_url, &session);
+            for day in days {
+                match client::fetch_input(&mut client, day, &input_dir, force)? {
+                    Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+                    Fetched::Cached(path) => println!(
+                        "Already have {}, use --force to download it again",
+                        path.display()
+                    ),
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input_dir,
+            input,
+            guesses,
+            session_file,
+            base_url,
+        } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let path = input.unwrap_or_else(|| input_dir.join(format!("day{}.txt", day)));
+                    solve(day, part, &path)?
+                }
+            };
+            let guesses_path = guesses.unwrap_or_else(|| input_dir.join("guesses.json"));
+            let mut guesses = Guesses::load(&guesses_path)?;
+
+            let session = client::session_token(session_file.as_deref())?;
+            let mut client = Client::new(&base_url, &session);
+            let outcome = client::submit_answer(&mut client, &mut guesses, day, part, &answer)?;
+            println!("Day {} part {}: {} is {}", day, part, answer, outcome);
+
+            Ok(match outcome {
+                Outcome::Correct | Outcome::AlreadySolved => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            })
+        }
+        Command::Gen {
+            day,
+            seed,
+            size,
+            cols,
+        } => {
+            let generator = gen::generator(day)?;
+            print!("{}", generator.generate(seed, Size { n: size, cols }));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Generators => {
+            for generator in gen::GENERATORS {
+                println!("day {}: --size is {}", generator.day, generator.size);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Check { day, seeds, size } => {
+            let differential = oracle::differential(day)
+                .with_context(|| format!("no reference solution for day {}", day))?;
+            // Shrinking tries inputs that make the parsers panic, those are just skipped.
+            runner::capture_panics();
+            match differential.check(0..seeds, size)? {
+                Some(mismatch) => {
+                    println!("{}", mismatch);
+                    Ok(ExitCode::FAILURE)
+                }
+                None => {
+                    println!("day {}: no mismatches", day);
+                    Ok(ExitCode::SUCCESS)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use aoc2023::day20::{parse_input, part1, part2};
+
+fn main() {
+    let stdin_input = std::io::read_to_string(std::io::stdin()).unwrap();
+    let input = parse_input(&stdin_input).unwrap();
+
+    // `day20 --dot | dot -Tsvg` to look at the module graph.
+    if std::env::args().any(|arg| arg == "--dot") {
+        println!("{}", input.to_dot());
+        return;
+    }
+
+    println!("Part1: {}", part1(&input).unwrap());
+    println!("Part2: {}", part2(&input).unwrap());
+}
@@ -0,0 +1,17 @@
+use aoc2023::day25::{parse_input, part1};
+
+fn main() {
+    let stdin_input = std::io::read_to_string(std::io::stdin()).unwrap();
+    let input = parse_input(&stdin_input).unwrap();
+    println!("Part1: {}", part1(&input).unwrap());
+
+    let cut = input.min_cut().unwrap();
+    println!(
+        "Groups: {} and {}",
+        cut.partition.0.len(),
+        cut.partition.1.len()
+    );
+    for (a, b) in input.cut_edges(&cut) {
+        println!("Cut: {}/{}", a, b);
+    }
+}
@@ -0,0 +1,638 @@
+//! A small client for adventofcode.com, used by `aoc fetch` and `aoc submit`.
+//!
+//! Requests carry a User-Agent pointing back at this repo (as the site asks automated tools
+//! to do), and are spaced at least [`Client::min_interval`] apart.
+
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2023;
+pub const USER_AGENT: &str = concat!(
+    "aoc2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/omerbenamram/aoc2023)"
+);
+
+/// Environment variable holding the value of the `session` cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// The session token, from `$AOC_SESSION`, or else from `file`
+/// (defaulting to `~/.config/aoc/session`).
+pub fn session_token(file: Option<&Path>) -> Result<String> {
+    if let Ok(token) = std::env::var(SESSION_ENV) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let path = match file {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let home = std::env::var_os("HOME").context("HOME is not set")?;
+            PathBuf::from(home).join(".config/aoc/session")
+        }
+    };
+
+    let token = std::fs::read_to_string(&path)
+        .with_context(|| format!("no session token in ${} or {}", SESSION_ENV, path.display()))?;
+    let token = token.trim();
+    anyhow::ensure!(!token.is_empty(), "{} is empty", path.display());
+    Ok(token.to_string())
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            min_interval: Duration::from_secs(5),
+            last_request: None,
+        }
+    }
+
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            let since = last.elapsed();
+            if since < self.min_interval {
+                thread::sleep(self.min_interval - since);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    /// GETs `path`, or POSTs `form` to it if there is one.
+    fn request(&mut self, path: &str, form: Option<&[(&str, &str)]>) -> Result<String> {
+        self.throttle();
+
+        let url = format!("{}{}", self.base_url, path);
+        let cookie = format!("session={}", self.session);
+        let response = match form {
+            Some(form) => self.agent.post(&url).set("Cookie", &cookie).send_form(form),
+            None => self.agent.get(&url).set("Cookie", &cookie).call(),
+        };
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("reading {}", url)),
+            Err(ureq::Error::Status(400, _)) => {
+                bail!("{} rejected the session token, it may have expired", url)
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                bail!("{} not found, the puzzle may not be unlocked yet", url)
+            }
+            Err(e) => Err(e).with_context(|| format!("requesting {}", url)),
+        }
+    }
+
+    pub fn input(&mut self, day: u32) -> Result<String> {
+        self.request(&format!("/{}/day/{}/input", YEAR, day), None)
+    }
+
+    pub fn submit(&mut self, day: u32, part: u32, answer: &str) -> Result<Outcome> {
+        let level = part.to_string();
+        let page = self.request(
+            &format!("/{}/day/{}/answer", YEAR, day),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+        Outcome::parse(&page)
+    }
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Wrong,
+    /// Another answer was submitted too recently, nothing was checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part is already solved, nothing was checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Parses the `<article>` of the answer page.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if page.contains("You gave an answer too recently") {
+            let seconds = parse_wait(page).context("no wait time on the answer page")?;
+            Ok(Outcome::Wait { seconds })
+        } else if page.contains("Did you already complete it?") {
+            Ok(Outcome::AlreadySolved)
+        } else if page.contains("your answer is too high") {
+            Ok(Outcome::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Ok(Outcome::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Ok(Outcome::Wrong)
+        } else {
+            bail!("unrecognized answer page:\n{}", page)
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait { seconds } => write!(f, "submitted too recently, wait {}s", seconds),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// The wait in "You have 1m 23s left to wait." (or "You have 23s left to wait.").
+fn parse_wait(page: &str) -> Option<u64> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("You have ")? + "You have ".len();
+    page[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (n, unit) = part.split_at(part.len().checked_sub(1)?);
+            let n: u64 = n.parse().ok()?;
+            match unit {
+                "h" => Some(n * 3600),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+/// The answers submitted so far, kept in a JSON file so wrong ones are never sent twice.
+#[derive(Debug, Default)]
+pub struct Guesses {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    /// Loads the guesses from `path`, starting empty if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let guesses = match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("parsing {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        };
+        Ok(Guesses {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    pub fn for_part(&self, day: u32, part: u32) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| g.day == day && g.part == part)
+    }
+
+    /// Errors if `answer` is known to be wrong without asking: it was already rejected,
+    /// the part is solved, or it's outside the bracket left by the too high / too low guesses.
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<()> {
+        let mut low = None;
+        let mut high = None;
+        for guess in self.for_part(day, part) {
+            match guess.outcome {
+                Outcome::Correct => {
+                    bail!(
+                        "day {} part {} was already solved with {}",
+                        day,
+                        part,
+                        guess.answer
+                    )
+                }
+                Outcome::AlreadySolved => bail!("day {} part {} was already solved", day, part),
+                _ => {}
+            }
+            if guess.answer == answer {
+                bail!("{} was already submitted: {}", answer, guess.outcome);
+            }
+
+            let Ok(n) = guess.answer.parse::<i128>() else {
+                continue;
+            };
+            match guess.outcome {
+                Outcome::TooLow => low = low.max(Some(n)),
+                Outcome::TooHigh => high = Some(high.map_or(n, |h: i128| h.min(n))),
+                _ => {}
+            }
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            if let Some(low) = low.filter(|&low| n <= low) {
+                bail!("{} can't be right, {} was already too low", answer, low);
+            }
+            if let Some(high) = high.filter(|&high| n >= high) {
+                bail!("{} can't be right, {} was already too high", answer, high);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a checked answer and saves the file. Waits tell nothing about the answer,
+    /// so they aren't recorded.
+    pub fn record(&mut self, day: u32, part: u32, answer: &str, outcome: Outcome) -> Result<()> {
+        if matches!(outcome, Outcome::Wait { .. }) {
+            return Ok(());
+        }
+
+        self.guesses.push(Guess {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        });
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.guesses)?;
+        std::fs::write(&self.path, json).with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+/// Submits `answer` unless `guesses` already rules it out, and records the outcome.
+pub fn submit_answer(
+    client: &mut Client,
+    guesses: &mut Guesses,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Outcome> {
+    guesses.check(day, part, answer)?;
+    let outcome = client.submit(day, part, answer)?;
+    guesses.record(day, part, answer, outcome)?;
+    Ok(outcome)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already there, nothing was requested.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input of `day` into `input_dir/dayN.txt`,
+/// unless it's already there and `force` is off.
+pub fn fetch_input(
+    client: &mut Client,
+    day: u32,
+    input_dir: &Path,
+    force: bool,
+) -> Result<Fetched> {
+    let path = input_dir.join(format!("day{}.txt", day));
+    if path.exists() && !force {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(day)?;
+
+    // Write to a temporary file first, so a failed write never leaves half an input behind.
+    std::fs::create_dir_all(input_dir)
+        .with_context(|| format!("creating {}", input_dir.display()))?;
+    let tmp = path.with_extension("txt.tmp");
+    std::fs::write(&tmp, input).with_context(|| format!("writing {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("writing {}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, JoinHandle};
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// A one-thread HTTP server on localhost, answering each request with `respond`
+    /// and keeping the requests around for assertions.
+    pub struct StubServer {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<Request>>>,
+        _handle: JoinHandle<()>,
+    }
+
+    impl StubServer {
+        pub fn start(respond: impl Fn(&Request) -> (u16, String) + Send + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(vec![]));
+
+            let recorded = requests.clone();
+            let handle = thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { return };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap_or_default().to_string();
+                    let path = parts.next().unwrap_or_default().to_string();
+
+                    let mut headers = vec![];
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            headers.push((name.trim().to_string(), value.trim().to_string()));
+                        }
+                    }
+
+                    let mut request = Request {
+                        method,
+                        path,
+                        headers,
+                        body: String::new(),
+                    };
+                    let length = request
+                        .header("Content-Length")
+                        .and_then(|l| l.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    let mut body = vec![0; length];
+                    std::io::Read::read_exact(&mut reader, &mut body).unwrap();
+                    request.body = String::from_utf8(body).unwrap();
+
+                    let (status, body) = respond(&request);
+                    recorded.lock().unwrap().push(request);
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} STUB\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                }
+            });
+
+            StubServer {
+                url,
+                requests,
+                _handle: handle,
+            }
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stub::StubServer;
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2023-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let server = StubServer::start(|request| match request.path.as_str() {
+            "/2023/day/5/input" => (200, "seeds: 1 2\n".to_string()),
+            _ => (404, "404 Not Found".to_string()),
+        });
+        let dir = temp_dir("fetch");
+        let mut client = Client::new(&server.url, "abc").min_interval(Duration::ZERO);
+
+        let fetched = fetch_input(&mut client, 5, &dir, false).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(dir.join("day5.txt")));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day5.txt")).unwrap(),
+            "seeds: 1 2\n"
+        );
+
+        // Cached, so no second request, and local edits are kept.
+        std::fs::write(dir.join("day5.txt"), "edited").unwrap();
+        let fetched = fetch_input(&mut client, 5, &dir, false).unwrap();
+        assert_eq!(fetched, Fetched::Cached(dir.join("day5.txt")));
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day5.txt")).unwrap(),
+            "edited"
+        );
+
+        let fetched = fetch_input(&mut client, 5, &dir, true).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(dir.join("day5.txt")));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day5.txt")).unwrap(),
+            "seeds: 1 2\n"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+        assert_eq!(requests[0].header("User-Agent"), Some(USER_AGENT));
+
+        let error = fetch_input(&mut client, 6, &dir, false).unwrap_err();
+        assert!(error.to_string().contains("not be unlocked yet"));
+        assert!(!dir.join("day6.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = StubServer::start(|_| (200, "input".to_string()));
+        let mut client = Client::new(&server.url, "abc").min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.input(1).unwrap();
+        client.input(2).unwrap();
+        client.input(3).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(400));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_outcome_parse() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        let cases = [
+            (
+                "That's the right answer!  You are one gold star closer to restoring snow operations.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, ...",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.  If you're stuck, ...",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before \
+                 trying again.  You have 1m 23s left to wait.",
+                Outcome::Wait { seconds: 83 },
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before \
+                 trying again.  You have 9s left to wait.",
+                Outcome::Wait { seconds: 9 },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+        ];
+
+        for (text, outcome) in cases {
+            assert_eq!(Outcome::parse(&page(text)).unwrap(), outcome, "{}", text);
+        }
+        assert!(Outcome::parse(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        // The answer is 42, and only one answer may be submitted every other request.
+        let server = StubServer::start(|request| {
+            let text = if request.body.contains("answer=1000") {
+                "You gave an answer too recently; You have 30s left to wait."
+            } else if request.body.contains("answer=42") {
+                "That's the right answer!"
+            } else if request.body.contains("answer=100") {
+                "That's not the right answer; your answer is too high."
+            } else if request.body.contains("answer=10") {
+                "That's not the right answer; your answer is too low."
+            } else {
+                "That's not the right answer."
+            };
+            (200, format!("<article><p>{}</p></article>", text))
+        });
+        let dir = temp_dir("submit");
+        let path = dir.join("guesses.json");
+        let mut client = Client::new(&server.url, "abc").min_interval(Duration::ZERO);
+        let mut guesses = Guesses::load(&path).unwrap();
+
+        let mut submit =
+            |guesses: &mut Guesses, answer: &str| submit_answer(&mut client, guesses, 3, 1, answer);
+
+        assert_eq!(
+            submit(&mut guesses, "1000").unwrap(),
+            Outcome::Wait { seconds: 30 }
+        );
+        assert_eq!(submit(&mut guesses, "100").unwrap(), Outcome::TooHigh);
+        assert_eq!(submit(&mut guesses, "10").unwrap(), Outcome::TooLow);
+        assert_eq!(submit(&mut guesses, "ten").unwrap(), Outcome::Wrong);
+        assert_eq!(server.requests().len(), 4);
+
+        // Reloaded from the file: nothing the guesses rule out reaches the server.
+        let mut guesses = Guesses::load(&path).unwrap();
+        assert_eq!(guesses.for_part(3, 1).count(), 3);
+        for answer in ["100", "10", "ten", "5", "101", "1000"] {
+            assert!(submit(&mut guesses, answer).is_err(), "{}", answer);
+        }
+        assert_eq!(server.requests().len(), 4);
+
+        // Other parts have their own guesses.
+        assert!(guesses.check(3, 2, "100").is_ok());
+
+        assert_eq!(submit(&mut guesses, "42").unwrap(), Outcome::Correct);
+        let error = submit(&mut guesses, "43").unwrap_err();
+        assert!(error.to_string().contains("already solved with 42"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[4].method, "POST");
+        assert_eq!(requests[4].path, "/2023/day/3/answer");
+        assert_eq!(requests[4].body, "level=1&answer=42");
+        assert_eq!(requests[4].header("Cookie"), Some("session=abc"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_token_file() {
+        // Only meaningful without the variable, which tests shouldn't depend on.
+        if std::env::var(SESSION_ENV).is_ok() {
+            return;
+        }
+
+        let dir = temp_dir("session");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session");
+        std::fs::write(&path, "  token\n").unwrap();
+        assert_eq!(session_token(Some(&path)).unwrap(), "token");
+
+        std::fs::write(&path, "\n").unwrap();
+        assert!(session_token(Some(&path)).is_err());
+        assert!(session_token(Some(&dir.join("missing"))).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,723 @@
+use itertools::Itertools;
+use regex::Regex;
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+
+pub type Input<'a> = Vec<&'a str>;
+
+pub fn parse_input(s: &str) -> Result<Input<'_>> {
+    Ok(s.lines().collect())
+}
+
+pub fn part1(calibration_values: &[&str]) -> Result<u64> {
+    calibrate(calibration_values, &DIGIT_MATCHER).total()
+}
+
+/// The words that spell out digits in a puzzle, e.g. `one` to `nine` in English.
+///
+/// The digits `0` to `9` themselves are always recognized, so they aren't part of the lexicon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitLexicon {
+    words: Vec<(String, i32)>,
+}
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const SPANISH: [&str; 9] = [
+    "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+
+impl DigitLexicon {
+    /// A lexicon from a word to digit table.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, i32)>) -> Result<Self> {
+        let mut lexicon = DigitLexicon { words: vec![] };
+        for (word, digit) in words {
+            let word = word.into();
+            if word.is_empty() {
+                bail!("Digit words can't be empty");
+            }
+            if !(0..=9).contains(&digit) {
+                bail!("`{}` maps to {}, which is not a digit", word, digit);
+            }
+            match lexicon.word(&word) {
+                Some(other) if other != digit => {
+                    bail!("`{}` maps to both {} and {}", word, other, digit)
+                }
+                Some(_) => {}
+                None => lexicon.words.push((word, digit)),
+            }
+        }
+        Ok(lexicon)
+    }
+
+    /// One to nine, from a table ordered from one.
+    fn builtin(table: [&str; 9]) -> Self {
+        let words = table.iter().zip(1..);
+        Self::new(words.map(|(w, d)| (*w, d))).expect("built-in tables are valid")
+    }
+
+    /// `one` to `nine`, the lexicon of the actual puzzle.
+    pub fn english() -> Self {
+        Self::builtin(ENGLISH)
+    }
+
+    pub fn spanish() -> Self {
+        Self::builtin(SPANISH)
+    }
+
+    pub fn german() -> Self {
+        Self::builtin(GERMAN)
+    }
+
+    /// The same lexicon plus `word` for zero, e.g. `with_zero("cero")` for Spanish.
+    /// Fails like [`Self::new`] if the word is empty or already maps to another digit.
+    pub fn with_zero(self, word: &str) -> Result<Self> {
+        Self::new(self.words.into_iter().chain([(word.to_string(), 0)]))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, i32)> {
+        self.words.iter().map(|(w, d)| (w.as_str(), *d))
+    }
+
+    fn word(&self, word: &str) -> Option<i32> {
+        self.words().find(|(w, _)| *w == word).map(|(_, d)| d)
+    }
+
+    /// The value of a single digit or word.
+    pub fn digit(&self, s: &str) -> Result<i32> {
+        match s.as_bytes() {
+            [] => bail!("Empty string"),
+            [d @ b'0'..=b'9'] => Ok((d - b'0') as i32),
+            _ => self
+                .word(s)
+                .ok_or_else(|| anyhow::anyhow!("`{}` is not a number", s)),
+        }
+    }
+
+    /// A regex matching any digit or word, longest words first so none is cut short.
+    pub fn regex(&self) -> Regex {
+        let words = self
+            .words()
+            .map(|(w, _)| w)
+            .sorted_by_key(|w| std::cmp::Reverse(w.len()));
+        let pattern = std::iter::once("\\d".to_string())
+            .chain(words.map(regex::escape))
+            .join("|");
+        Regex::new(&format!("({})", pattern)).expect("regex is valid")
+    }
+
+    /// An Aho-Corasick matcher for the digits and words.
+    pub fn matcher(&self) -> DigitMatcher {
+        DigitMatcher::new(self.words())
+    }
+}
+
+/// Aho-Corasick automata over the digits and the digit words, so a line is read once, a byte at
+/// a time, and overlapping words ("eightwo") are all found without allocating.
+///
+/// The first digit is found scanning from the left, and the last one with a second automaton over
+/// the reversed words, scanning from the right, so lines are usually only read at both ends.
+pub struct DigitMatcher {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitMatch {
+    pub digit: i32,
+    /// Byte offsets of the digit or word in the line, half-open.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Only `0` to `9`, for part 1.
+pub static DIGIT_MATCHER: Lazy<DigitMatcher> = Lazy::new(|| DigitMatcher::new([]));
+/// `0` to `9` and `one` to `nine`, for part 2.
+pub static ENGLISH_MATCHER: Lazy<DigitMatcher> = Lazy::new(|| DigitLexicon::english().matcher());
+
+impl DigitMatcher {
+    /// A matcher for `0` to `9` and the given words, see [`DigitLexicon::matcher`].
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, i32)>) -> Self {
+        let digits = (0..10).map(|digit| (digit.to_string(), digit));
+        let words = digits
+            .chain(words.into_iter().map(|(w, d)| (w.to_string(), d)))
+            .collect_vec();
+
+        let reversed = words
+            .iter()
+            .map(|(w, d)| (w.bytes().rev().collect_vec(), *d));
+        DigitMatcher {
+            forward: Automaton::new(words.iter().map(|(w, d)| (w.bytes().collect_vec(), *d))),
+            backward: Automaton::new(reversed),
+        }
+    }
+
+    /// All the digits and words in the line, overlapping ones included, in the order they end.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = DigitMatch> + 'a {
+        self.forward
+            .hits(line.bytes())
+            .map(|(end, len, digit)| DigitMatch {
+                digit,
+                start: end - len,
+                end,
+            })
+    }
+
+    /// The digit or word that starts first, the longest one if several start there.
+    ///
+    /// That's usually the first hit, but a longer word can start before a shorter one ends
+    /// (`abcd` and `bc`), so the scan goes on while a word starting earlier could still end.
+    pub fn first(&self, line: &str) -> Option<DigitMatch> {
+        let mut first: Option<DigitMatch> = None;
+        let mut state = 0;
+        for (i, b) in line.bytes().enumerate() {
+            state = self.forward.step(state, b);
+            let end = i + 1;
+            // Whatever ends from here on starts inside the current state's prefix.
+            if first.is_some_and(|first| end - self.forward.nodes[state].depth > first.start) {
+                break;
+            }
+            for (len, digit) in self.forward.outputs(state) {
+                let start = end - len;
+                // Hits come in the order they end, so at the same start this one is longer.
+                if first.map_or(true, |first| start <= first.start) {
+                    first = Some(DigitMatch { digit, start, end });
+                }
+            }
+        }
+        first
+    }
+
+    /// The digit or word that starts last, the longest one if several start there.
+    ///
+    /// Starting last is ending first in the reversed line, so that's the first hit of the
+    /// reversed words, and the longest hit there.
+    pub fn last(&self, line: &str) -> Option<DigitMatch> {
+        let (end, len, digit) = self.backward.hits(line.bytes().rev()).next()?;
+        Some(DigitMatch {
+            digit,
+            start: line.len() - end,
+            end: line.len() - end + len,
+        })
+    }
+}
+
+/// An Aho-Corasick automaton over byte strings that each map to a digit.
+struct Automaton {
+    nodes: Vec<Node>,
+}
+
+#[derive(Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    /// The node of the longest proper suffix of this node's prefix that is in the trie.
+    fail: usize,
+    /// The node of the longest proper suffix that is a whole word, found via `fail`.
+    output: Option<usize>,
+    /// Set if a word ends here.
+    digit: Option<i32>,
+    /// The length of the prefix.
+    depth: usize,
+}
+
+impl Automaton {
+    fn new(words: impl IntoIterator<Item = (Vec<u8>, i32)>) -> Self {
+        let mut automaton = Automaton {
+            nodes: vec![Node::default()],
+        };
+        for (word, digit) in words {
+            automaton.insert(&word, digit);
+        }
+
+        // Breadth first, so the suffixes a node falls back to are done before it.
+        let mut queue = VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            for (b, child) in automaton.nodes[node].children.clone() {
+                let fail = if node == 0 {
+                    0
+                } else {
+                    automaton.step(automaton.nodes[node].fail, b)
+                };
+                let output = match automaton.nodes[fail].digit {
+                    Some(_) => Some(fail),
+                    None => automaton.nodes[fail].output,
+                };
+                automaton.nodes[child].fail = fail;
+                automaton.nodes[child].output = output;
+                queue.push_back(child);
+            }
+        }
+
+        automaton
+    }
+
+    fn insert(&mut self, word: &[u8], digit: i32) {
+        let mut node = 0;
+        for (depth, &b) in word.iter().enumerate() {
+            node = match self.child(node, b) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node {
+                        depth: depth + 1,
+                        ..Node::default()
+                    });
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((b, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].digit = Some(digit);
+    }
+
+    fn child(&self, node: usize, b: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(c, _)| *c == b)
+            .map(|(_, child)| *child)
+    }
+
+    /// The state after reading `b` in `node`, falling back along the failure links.
+    fn step(&self, mut node: usize, b: u8) -> usize {
+        loop {
+            if let Some(child) = self.child(node, b) {
+                return child;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    /// The (length, digit) of every word ending in `node`, longest first.
+    fn outputs(&self, node: usize) -> impl Iterator<Item = (usize, i32)> + '_ {
+        let longest = match self.nodes[node].digit {
+            Some(_) => Some(node),
+            None => self.nodes[node].output,
+        };
+        std::iter::successors(longest, |&node| self.nodes[node].output).map(|node| {
+            let digit = self.nodes[node].digit.expect("outputs end a word");
+            (self.nodes[node].depth, digit)
+        })
+    }
+
+    /// The (end, length, digit) of every word in `bytes`, in the order they end,
+    /// longest first when several end together.
+    fn hits<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = (usize, usize, i32)> + 'a {
+        bytes
+            .enumerate()
+            .scan(0, |state, (i, b)| {
+                *state = self.step(*state, b);
+                Some((i + 1, *state))
+            })
+            .flat_map(|(end, state)| {
+                self.outputs(state)
+                    .map(move |(len, digit)| (end, len, digit))
+            })
+    }
+}
+
+pub fn part2(calibration_values: &[&str]) -> Result<u64> {
+    part2_with(calibration_values, &ENGLISH_MATCHER)
+}
+
+/// Part 2 with the digit words of another lexicon, e.g.
+/// `part2_with(lines, &DigitLexicon::german().with_zero("null")?.matcher())`.
+pub fn part2_with(calibration_values: &[&str], matcher: &DigitMatcher) -> Result<u64> {
+    calibrate(calibration_values, matcher).total()
+}
+
+/// How the calibration value of a line came about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCalibration {
+    pub first: DigitMatch,
+    pub last: DigitMatch,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoDigit {
+    /// 1-based.
+    pub line: usize,
+}
+
+impl std::fmt::Display for NoDigit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: expected at least one digit", self.line)
+    }
+}
+
+/// The calibration of every line, in order, including the ones that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub lines: Vec<Result<LineCalibration, NoDigit>>,
+}
+
+impl Calibration {
+    pub fn errors(&self) -> impl Iterator<Item = &NoDigit> {
+        self.lines.iter().filter_map(|l| l.as_ref().err())
+    }
+
+    /// The sum of the lines that have a value.
+    pub fn sum(&self) -> u64 {
+        self.lines.iter().flatten().map(|l| l.value as u64).sum()
+    }
+
+    /// The sum, or all the lines without a digit.
+    pub fn total(&self) -> Result<u64> {
+        total(self.sum(), self.errors())
+    }
+}
+
+fn total<'a>(sum: u64, errors: impl Iterator<Item = &'a NoDigit>) -> Result<u64> {
+    let errors = errors.map(|e| e.line).collect_vec();
+    match errors.as_slice() {
+        [] => Ok(sum),
+        [line] => bail!("Expected at least one digit on line {}", line),
+        _ => bail!(
+            "Expected at least one digit on lines {}",
+            errors.iter().join(", ")
+        ),
+    }
+}
+
+fn calibrate_line(line: &str, matcher: &DigitMatcher) -> Option<LineCalibration> {
+    let (first, last) = (matcher.first(line)?, matcher.last(line)?);
+    Some(LineCalibration {
+        first,
+        last,
+        value: first.digit * 10 + last.digit,
+    })
+}
+
+pub fn calibrate(calibration_values: &[&str], matcher: &DigitMatcher) -> Calibration {
+    let lines = calibration_values
+        .iter()
+        .enumerate()
+        .map(|(i, line)| calibrate_line(line, matcher).ok_or(NoDigit { line: i + 1 }))
+        .collect();
+    Calibration { lines }
+}
+
+/// The outcome of a calibration that doesn't keep the lines around.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalibrationSum {
+    pub sum: u64,
+    pub lines: usize,
+    pub errors: Vec<NoDigit>,
+}
+
+impl CalibrationSum {
+    fn add(&mut self, line: &str, matcher: &DigitMatcher) {
+        self.lines += 1;
+        match calibrate_line(line, matcher) {
+            Some(calibration) => self.sum += calibration.value as u64,
+            None => self.errors.push(NoDigit { line: self.lines }),
+        }
+    }
+
+    /// Appends the lines of `other`, which come after these ones.
+    fn extend(&mut self, other: CalibrationSum) {
+        self.sum += other.sum;
+        self.errors
+            .extend(other.errors.into_iter().map(|e| NoDigit {
+                line: e.line + self.lines,
+            }));
+        self.lines += other.lines;
+    }
+
+    /// The sum, or all the lines without a digit.
+    pub fn total(&self) -> Result<u64> {
+        total(self.sum, self.errors.iter())
+    }
+}
+
+/// Reads a line without its line ending into `buf`, `false` at the end of the input.
+fn read_line(reader: &mut impl BufRead, buf: &mut String) -> Result<bool> {
+    buf.clear();
+    if reader.read_line(buf)? == 0 {
+        return Ok(false);
+    }
+    let len = buf.trim_end_matches(['\n', '\r']).len();
+    buf.truncate(len);
+    Ok(true)
+}
+
+/// Calibrates the lines of `reader` with every matcher (e.g. one per part) in a single pass,
+/// holding only one line in memory at a time.
+pub fn calibrate_reader<const N: usize>(
+    mut reader: impl BufRead,
+    matchers: [&DigitMatcher; N],
+) -> Result<[CalibrationSum; N]> {
+    let mut sums = std::array::from_fn(|_| CalibrationSum::default());
+    let mut line = String::new();
+    while read_line(&mut reader, &mut line)? {
+        for (sum, matcher) in sums.iter_mut().zip(matchers) {
+            sum.add(&line, matcher);
+        }
+    }
+    Ok(sums)
+}
+
+/// Like [`calibrate_reader`], but reads chunks of about `chunk_size` bytes (cut at line ends)
+/// and calibrates a chunk per rayon thread at a time, so memory stays at a few chunks.
+pub fn calibrate_reader_par<const N: usize>(
+    mut reader: impl BufRead,
+    matchers: [&DigitMatcher; N],
+    chunk_size: usize,
+) -> Result<[CalibrationSum; N]> {
+    let mut sums = std::array::from_fn(|_| CalibrationSum::default());
+    let mut line = String::new();
+    let mut done = false;
+
+    while !done {
+        let mut chunks = vec![];
+        while !done && chunks.len() < rayon::current_num_threads() {
+            let mut chunk = String::with_capacity(chunk_size);
+            while chunk.len() < chunk_size.max(1) {
+                if !read_line(&mut reader, &mut line)? {
+                    done = true;
+                    break;
+                }
+                chunk.push_str(&line);
+                chunk.push('\n');
+            }
+            chunks.push(chunk);
+        }
+
+        let chunk_sums = chunks
+            .par_iter()
+            .map(|chunk| {
+                let mut sums = std::array::from_fn(|_| CalibrationSum::default());
+                for line in chunk.lines() {
+                    for (sum, matcher) in sums.iter_mut().zip(matchers) {
+                        sum.add(line, matcher);
+                    }
+                }
+                sums
+            })
+            .collect::<Vec<[CalibrationSum; N]>>();
+
+        for chunk_sums in chunk_sums {
+            for (sum, chunk_sum) in sums.iter_mut().zip(chunk_sums) {
+                sum.extend(chunk_sum);
+            }
+        }
+    }
+
+    Ok(sums)
+}
+
+/// The original part 2, restarting a regex search after every match to find overlapping ones.
+/// Kept as a reference for [`part2`], and as the baseline of `benches/day01.rs`.
+pub fn part2_regex(calibration_values: &[&str], lexicon: &DigitLexicon) -> Result<u64> {
+    let re = lexicon.regex();
+
+    let mut sum = 0;
+
+    for line in calibration_values {
+        let mut start = 0;
+        let mut matches = vec![];
+
+        // allow overlapping matches
+        while let Some(mat) = re.find(&line[start..]) {
+            matches.push(mat);
+            start += mat.start() + 1;
+        }
+
+        if matches.is_empty() {
+            bail!("Expected at least one digit");
+        }
+
+        let code1 = matches.first().expect("non empty").as_str();
+        let code2 = matches.last().expect("non empty").as_str();
+
+        sum += format!("{}{}", lexicon.digit(code1)?, lexicon.digit(code2)?)
+            .parse::<u64>()
+            .expect("two digits will produce a valid number");
+    }
+
+    Ok(sum)
+}
+
+#[test]
+fn test_part2() {
+    let input = "two1nine
+    eightwothree
+    abcone2threexyz
+    xtwone3four
+    4nineeightseven2
+    zoneight234
+    7pqrstsixteen
+    eighthree"; // overlapping
+
+    assert_eq!(
+        part2(&input.lines().filter(|line| !line.is_empty()).collect_vec()).unwrap(),
+        281 + 83
+    );
+}
+
+#[test]
+fn test_digit_matcher() {
+    let matcher = DigitLexicon::english().matcher();
+    let line = "xtwone3eighthree";
+
+    let matches = matcher.matches(line).map(|m| m.digit).collect_vec();
+    assert_eq!(matches, vec![2, 1, 3, 8, 3]);
+
+    let first = matcher.first(line).unwrap();
+    assert_eq!((first.digit, &line[first.start..first.end]), (2, "two"));
+    let last = matcher.last(line).unwrap();
+    assert_eq!((last.digit, &line[last.start..last.end]), (3, "three"));
+
+    assert_eq!(matcher.first("abcdef"), None);
+    assert_eq!(matcher.last("thre"), None);
+    assert_eq!(matcher.last("7"), matcher.first("7"));
+
+    // `bc` is the first hit, but `abcd` starts first. At the end, `cd` starts last.
+    let matcher = DigitLexicon::new([("abcd", 1), ("bc", 2), ("cd", 3)])
+        .unwrap()
+        .matcher();
+    let matches = matcher.matches("xabcdx").map(|m| m.digit).collect_vec();
+    assert_eq!(matches, vec![2, 1, 3]);
+    let first = matcher.first("xabcdx").unwrap();
+    assert_eq!((first.digit, first.start, first.end), (1, 1, 5));
+    let last = matcher.last("xabcdx").unwrap();
+    assert_eq!((last.digit, last.start, last.end), (3, 3, 5));
+
+    // Words that are suffixes of others come out through the output links.
+    let matcher = DigitLexicon::new([("uno", 1), ("unos", 2), ("nos", 3), ("s", 4)])
+        .unwrap()
+        .matcher();
+    let matches = matcher
+        .matches("unos")
+        .map(|m| (m.digit, m.start, m.end))
+        .collect_vec();
+    assert_eq!(matches, vec![(1, 0, 3), (2, 0, 4), (3, 1, 4), (4, 3, 4)]);
+    assert_eq!(matcher.first("unos").unwrap().digit, 2);
+    assert_eq!(matcher.last("unos").unwrap().digit, 4);
+}
+
+#[test]
+fn test_lexicons() {
+    let lines = [
+        "dosieteuno",
+        "xfünfzweiundzwanzigy",
+        "nullachtneun0",
+        "cerotres",
+    ];
+
+    let spanish = DigitLexicon::spanish();
+    assert_eq!(part2_with(&lines[..1], &spanish.matcher()).unwrap(), 21);
+    assert_eq!(part2_regex(&lines[..1], &spanish).unwrap(), 21);
+    assert_eq!(part2_with(&lines[3..], &spanish.matcher()).unwrap(), 33);
+    let spanish = spanish.with_zero("cero").unwrap();
+    assert_eq!(part2_with(&lines[3..], &spanish.matcher()).unwrap(), 3);
+    assert_eq!(part2_regex(&lines[3..], &spanish).unwrap(), 3);
+
+    let german = DigitLexicon::german().with_zero("null").unwrap();
+    assert_eq!(part2_with(&lines[1..3], &german.matcher()).unwrap(), 52);
+    assert_eq!(part2_regex(&lines[1..3], &german).unwrap(), 52);
+
+    // Made up words, where one is a prefix of another.
+    let custom = DigitLexicon::new([("uno", 1), ("unos", 2), ("x", 7)]).unwrap();
+    assert_eq!(part2_with(&["unos"], &custom.matcher()).unwrap(), 22);
+    assert_eq!(part2_regex(&["unos"], &custom).unwrap(), 22);
+    assert_eq!(part2_with(&["ax3"], &custom.matcher()).unwrap(), 73);
+    let zero = custom.clone().with_zero("nada").unwrap();
+    assert_eq!(part2_with(&["nada7"], &zero.matcher()).unwrap(), 7);
+    assert!(custom.clone().with_zero("uno").is_err());
+    assert!(custom.clone().with_zero("").is_err());
+    assert_eq!(
+        custom.clone().with_zero("x").unwrap_err().to_string(),
+        "`x` maps to both 7 and 0"
+    );
+
+    assert!(DigitLexicon::new([("ten", 10)]).is_err());
+    assert!(DigitLexicon::new([("", 1)]).is_err());
+    assert!(DigitLexicon::new([("one", 1), ("one", 2)]).is_err());
+    assert!(DigitLexicon::new([("one", 1), ("one", 1)]).is_ok());
+}
+
+#[test]
+fn test_calibrate() {
+    let lines = ["abc", "eighthree", "1x", "", "treb7uchet"];
+    let calibration = calibrate(&lines, &ENGLISH_MATCHER);
+
+    let eighthree = calibration.lines[1].unwrap();
+    assert_eq!(eighthree.value, 83);
+    assert_eq!((eighthree.first.start, eighthree.first.end), (0, 5));
+    assert_eq!((eighthree.last.start, eighthree.last.end), (4, 9));
+    assert_eq!(calibration.lines[2].unwrap().value, 11);
+
+    let errors = calibration.errors().map(|e| e.line).collect_vec();
+    assert_eq!(errors, vec![1, 4]);
+    assert_eq!(calibration.sum(), 83 + 11 + 77);
+    assert_eq!(
+        calibration.total().unwrap_err().to_string(),
+        "Expected at least one digit on lines 1, 4"
+    );
+
+    let error = part1(&lines[1..3]).unwrap_err();
+    assert_eq!(error.to_string(), "Expected at least one digit on line 1");
+}
+
+#[test]
+fn test_calibrate_reader() {
+    use crate::gen::{self, Size};
+
+    let mut input = gen::generator(1).unwrap().generate(
+        3,
+        Size {
+            n: 500,
+            cols: Some(30),
+        },
+    );
+    input.push_str("no digits here\r\nsevenine\n\nlast1");
+    let lines = parse_input(&input).unwrap();
+
+    let matchers = [&*DIGIT_MATCHER, &*ENGLISH_MATCHER];
+    let expected = matchers.map(|matcher| calibrate(&lines, matcher));
+    let sequential = calibrate_reader(input.as_bytes(), matchers).unwrap();
+    for chunk_size in [1, 64, 1000, 1 << 20] {
+        let parallel = calibrate_reader_par(input.as_bytes(), matchers, chunk_size).unwrap();
+        assert_eq!(parallel, sequential, "chunks of {}", chunk_size);
+    }
+
+    for (sum, calibration) in sequential.iter().zip(&expected) {
+        assert_eq!(sum.lines, 504);
+        assert_eq!(sum.sum, calibration.sum());
+        assert_eq!(sum.errors, calibration.errors().copied().collect_vec());
+    }
+    assert_eq!(
+        sequential[1].errors,
+        vec![NoDigit { line: 501 }, NoDigit { line: 503 }]
+    );
+}
+
+#[test]
+fn test_part1() {
+    let input = "1abc2
+    pqr3stu8vwx
+    a1b2c3d4e5f
+    treb7uchet";
+
+    assert_eq!(
+        part1(&input.lines().filter(|line| !line.is_empty()).collect_vec()).unwrap(),
+        142
+    );
+}
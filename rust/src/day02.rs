@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Error, Result};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+
+use crate::regex;
+
+/// An interned colour name, so any colour parses and copies and compares cheaply.
+/// Colours order by name, so maps of colours come out the same way every time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Color(&'static str);
+
+// Names are leaked the first time they're seen, there are only ever a handful of colours.
+static NAMES: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+impl Color {
+    pub fn new(name: &str) -> Self {
+        let mut names = NAMES.lock().unwrap();
+        if let Some(&name) = names.get(name) {
+            return Color(name);
+        }
+
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        names.insert(name);
+        Color(name)
+    }
+
+    pub fn name(self) -> &'static str {
+        self.0
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How many cubes of each colour were shown at once.
+pub type Showing = BTreeMap<Color, i32>;
+
+/// The cubes of each colour in the bag, in the order they were given.
+/// Colours that aren't in the bag have no cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag(Vec<(Color, i32)>);
+
+impl Bag {
+    /// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+    pub fn puzzle() -> Self {
+        Bag(vec![
+            (Color::new("red"), 12),
+            (Color::new("green"), 13),
+            (Color::new("blue"), 14),
+        ])
+    }
+
+    pub fn get(&self, color: Color) -> i32 {
+        self.cubes()
+            .find(|(c, _)| *c == color)
+            .map_or(0, |(_, count)| count)
+    }
+
+    /// Makes sure there are at least `count` cubes of `color`.
+    pub fn raise(&mut self, color: Color, count: i32) {
+        match self.0.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, cubes)) => *cubes = (*cubes).max(count),
+            None => self.0.push((color, count)),
+        }
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.0.iter().map(|(color, _)| *color)
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = (Color, i32)> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// The number of cubes of each colour in the bag multiplied together.
+    pub fn power(&self) -> i32 {
+        self.cubes().map(|(_, count)| count).product()
+    }
+}
+
+/// `red=12,green=13,blue=14`.
+impl FromStr for Bag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bag = Bag::default();
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (color, count) = entry
+                .split_once('=')
+                .with_context(|| format!("expected `color=count`, got `{}`", entry))?;
+            let count = count
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid count in `{}`", entry))?;
+            let color = Color::new(color.trim());
+            if bag.colors().contains(&color) {
+                bail!("`{}` is in the bag twice", color);
+            }
+            bag.0.push((color, count));
+        }
+        Ok(bag)
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self
+            .cubes()
+            .map(|(color, count)| format!("{}={}", color, count));
+        write!(f, "{}", entries.format(","))
+    }
+}
+
+/// Why a game is impossible with a bag: the first showing with more cubes of a colour
+/// than there are in the bag (the first such colour by name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Infeasible {
+    pub game: i32,
+    /// 1-based, in the order of the game.
+    pub showing: usize,
+    pub color: Color,
+    pub shown: i32,
+    pub in_bag: i32,
+}
+
+impl Display for Infeasible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "showing {} has {} {}, the bag has {}",
+            self.showing, self.shown, self.color, self.in_bag
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: i32,
+    pub showings: Vec<Showing>,
+}
+
+impl Game {
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.infeasible(bag).is_none()
+    }
+
+    /// The first showing and colour that the bag can't explain, if any.
+    pub fn infeasible(&self, bag: &Bag) -> Option<Infeasible> {
+        self.showings.iter().enumerate().find_map(|(i, showing)| {
+            showing.iter().find_map(|(&color, &shown)| {
+                let in_bag = bag.get(color);
+                (shown > in_bag).then_some(Infeasible {
+                    game: self.id,
+                    showing: i + 1,
+                    color,
+                    shown,
+                    in_bag,
+                })
+            })
+        })
+    }
+
+    /// The most cubes of `color` shown at once.
+    pub fn max_shown(&self, color: Color) -> i32 {
+        self.showings
+            .iter()
+            .filter_map(|showing| showing.get(&color))
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The smallest bag this game is possible with: the most cubes of each colour shown at once,
+    /// colours by name.
+    pub fn minimal_bag(&self) -> Bag {
+        let mut bag = Bag::default();
+        for (&color, &count) in self.showings.iter().flatten() {
+            bag.raise(color, count);
+        }
+        bag.0.sort();
+        bag
+    }
+
+    // The power of a set of cubes is equal to the numbers of red, green, and blue cubes multiplied together.
+    // With another bag, it's the colours of that bag, and a colour the game never shows counts as 0.
+    pub fn power(&self, bag: &Bag) -> i32 {
+        bag.colors().map(|color| self.max_shown(color)).product()
+    }
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = regex!(r"Game (?P<id>\d+): (?P<showings>.+)");
+        let re_showing = regex!(r"(?P<count>\d+) (?P<color>\w+)");
+
+        let caps = re
+            .captures(s)
+            .ok_or_else(|| Error::msg("Invalid game string"))?;
+
+        let id = caps["id"].parse()?;
+
+        let showings = caps["showings"]
+            .split(';')
+            .map(|showing| {
+                let mut cubes = Showing::new();
+                for cap in re_showing.captures_iter(showing) {
+                    let count: i32 = cap["count"].parse()?;
+                    *cubes.entry(Color::new(&cap["color"])).or_default() += count;
+                }
+                Ok(cubes)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Game { id, showings })
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Game>> {
+    s.lines().map(Game::from_str).collect()
+}
+
+/// The smallest bag every game is possible with, colours by name.
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    let mut bag = Bag::default();
+    for (color, count) in games.iter().flat_map(|game| game.minimal_bag().0) {
+        bag.raise(color, count);
+    }
+    bag.0.sort();
+    bag
+}
+
+/// The games that are impossible with `bag`, and why.
+pub fn infeasible(games: &[Game], bag: &Bag) -> Vec<Infeasible> {
+    games
+        .iter()
+        .filter_map(|game| game.infeasible(bag))
+        .collect()
+}
+
+pub fn part1(games: &[Game]) -> Result<i32> {
+    part1_with(games, &Bag::puzzle())
+}
+
+pub fn part2(games: &[Game]) -> Result<i32> {
+    part2_with(games, &Bag::puzzle())
+}
+
+pub fn part1_with(games: &[Game], bag: &Bag) -> Result<i32> {
+    Ok(games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum())
+}
+
+pub fn part2_with(games: &[Game], bag: &Bag) -> Result<i32> {
+    Ok(games.iter().map(|game| game.power(bag)).sum())
+}
+
+#[test]
+fn test() {
+    let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    let input = parse_input(input).unwrap();
+
+    assert_eq!(part1(&input).unwrap(), 8);
+    assert_eq!(part2(&input).unwrap(), 2286);
+}
+
+#[test]
+fn test_minimal_bag() {
+    let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    let games = parse_input(input).unwrap();
+    let [red, green, blue] = ["red", "green", "blue"].map(Color::new);
+
+    let bag = games[2].minimal_bag();
+    assert_eq!((bag.get(red), bag.get(green), bag.get(blue)), (20, 13, 6));
+    assert_eq!(bag.power(), games[2].power(&Bag::puzzle()));
+
+    let bag = minimal_bag(&games);
+    assert_eq!((bag.get(red), bag.get(green), bag.get(blue)), (20, 13, 15));
+    assert!(games.iter().all(|game| game.is_possible(&bag)));
+    assert!(infeasible(&games, &bag).is_empty());
+
+    let infeasible = infeasible(&games, &Bag::puzzle());
+    assert_eq!(
+        infeasible,
+        vec![
+            Infeasible {
+                game: 3,
+                showing: 1,
+                color: red,
+                shown: 20,
+                in_bag: 12,
+            },
+            Infeasible {
+                game: 4,
+                showing: 3,
+                color: blue,
+                shown: 15,
+                in_bag: 14,
+            },
+        ]
+    );
+    assert_eq!(
+        infeasible[1].to_string(),
+        "showing 3 has 15 blue, the bag has 14"
+    );
+}
+
+#[test]
+fn test_other_bags() {
+    let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 yellow; 3 yellow, 4 blue, 1 red; 1 yellow, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+    let input = parse_input(input).unwrap();
+    let yellow = Color::new("yellow");
+    assert_eq!(input[1].showings[1][&yellow], 3);
+    assert_eq!(input[1].max_shown(yellow), 3);
+    assert_eq!(yellow, Color::new("yellow"));
+    assert_eq!(yellow.name(), "yellow");
+
+    // Yellow isn't in the puzzle's bag, so game 2 is impossible.
+    // It never shows green, so its power with the puzzle's bag is 0.
+    assert_eq!(part1(&input).unwrap(), 1);
+    assert_eq!(input[1].power(&Bag::puzzle()), 0);
+    assert_eq!(part2(&input).unwrap(), 48 + 1560);
+
+    let bag: Bag = "red=20, green=13,blue=6,yellow=3".parse().unwrap();
+    assert_eq!(bag.to_string(), "red=20,green=13,blue=6,yellow=3");
+    assert_eq!(part1_with(&input, &bag).unwrap(), 1 + 2 + 3);
+
+    // Without green, only game 2 has all the colours, and only it is possible.
+    let bag: Bag = "red=20,blue=6,yellow=3".parse().unwrap();
+    assert_eq!(part1_with(&input, &bag).unwrap(), 2);
+    // 1 red, 4 blue and 3 yellow.
+    assert_eq!(input[1].power(&bag), 4 * 3);
+    assert_eq!(part2_with(&input, &bag).unwrap(), 4 * 3);
+
+    assert!("red=12,red=13".parse::<Bag>().is_err());
+    assert!("red".parse::<Bag>().is_err());
+    assert!("red=many".parse::<Bag>().is_err());
+}
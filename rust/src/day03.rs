@@ -0,0 +1,633 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    ops::{Range, RangeBounds},
+    str::FromStr,
+};
+
+use anyhow::Result;
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SchematicsSymbol {
+    /// The value and the columns it covers, half-open: `467..` at the start of a line is `0..3`.
+    Number((usize, Range<usize>)),
+    Symbol((char, usize)), // position
+}
+
+impl SchematicsSymbol {
+    /// The value, if this is a number.
+    pub fn number(&self) -> Option<usize> {
+        match self {
+            SchematicsSymbol::Number((n, _)) => Some(*n),
+            SchematicsSymbol::Symbol(_) => None,
+        }
+    }
+
+    /// The character, if this is a symbol.
+    pub fn symbol(&self) -> Option<char> {
+        match self {
+            SchematicsSymbol::Symbol((c, _)) => Some(*c),
+            SchematicsSymbol::Number(_) => None,
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.number().is_some()
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        self.symbol().is_some()
+    }
+}
+
+/// A symbol and the part numbers around it, from [`SchematicsGrid::gears`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol: char,
+    pub row: usize,
+    pub col: usize,
+    /// Row by row, left to right.
+    pub numbers: Vec<usize>,
+}
+
+impl Gear {
+    /// The part numbers multiplied together.
+    pub fn ratio(&self) -> u64 {
+        self.numbers.iter().map(|&n| n as u64).product()
+    }
+}
+
+/// The numbers and symbols of the schematic, indexed by the cells they cover,
+/// so adjacency is a handful of hash probes around an item.
+pub struct SchematicsGrid {
+    /// Every number and symbol, with the row it's on.
+    pub items: Vec<(usize, SchematicsSymbol)>,
+    /// The index in `items` of whatever covers a (row, column) cell. Blank cells aren't there.
+    cells: FxHashMap<(usize, usize), usize>,
+}
+
+/// Which characters count as symbols. Digits and `.` never do, and anything else that isn't
+/// in the set is blank.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SymbolSet {
+    /// Anything else that's visible. Whitespace and control characters are blank.
+    #[default]
+    Any,
+    Only(Vec<char>),
+}
+
+impl SymbolSet {
+    pub fn only(symbols: &str) -> Self {
+        SymbolSet::Only(symbols.chars().collect())
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            SymbolSet::Any => !c.is_whitespace() && !c.is_control(),
+            SymbolSet::Only(symbols) => symbols.contains(&c),
+        }
+    }
+}
+
+/// A character that is neither a digit, `.`, nor in the [`SymbolSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnexpectedGlyph {
+    pub row: usize,
+    /// In characters, not bytes.
+    pub col: usize,
+    pub glyph: char,
+}
+
+impl Display for UnexpectedGlyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 1-based, like an editor.
+        write!(
+            f,
+            "{:?} at line {}, column {}",
+            self.glyph,
+            self.row + 1,
+            self.col + 1
+        )
+    }
+}
+
+/// Every character of the schematic that isn't a digit, `.` or one of `symbols`,
+/// which [`SchematicsGrid::parse`] reads as blank.
+pub fn validate(s: &str, symbols: &SymbolSet) -> Vec<UnexpectedGlyph> {
+    s.lines()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| !c.is_ascii_digit() && c != '.' && !symbols.contains(c))
+                .map(move |(col, glyph)| UnexpectedGlyph { row, col, glyph })
+        })
+        .collect()
+}
+
+impl SchematicsGrid {
+    /// Parses a schematic with `symbols` as its symbols. Any other character that isn't a digit
+    /// is blank, like `.`; [`validate`] finds them.
+    pub fn parse(s: &str, symbols: &SymbolSet) -> Self {
+        let mut items = vec![];
+        for (row, line) in s.lines().enumerate() {
+            // (value, start column) of the number being read.
+            let mut number: Option<(usize, usize)> = None;
+            // Columns are characters, so a wide glyph doesn't shift what comes after it.
+            for (col, c) in line.chars().chain(['.']).enumerate() {
+                if let Some(digit) = c.to_digit(10) {
+                    let (value, _) = number.get_or_insert((0, col));
+                    *value = *value * 10 + digit as usize;
+                    continue;
+                }
+
+                if let Some((value, start)) = number.take() {
+                    items.push((row, SchematicsSymbol::Number((value, start..col))));
+                }
+                if c != '.' && symbols.contains(c) {
+                    items.push((row, SchematicsSymbol::Symbol((c, col))));
+                }
+            }
+        }
+
+        let covered = items.iter().map(|(_, item)| columns(item).len()).sum();
+        let mut cells = FxHashMap::with_capacity_and_hasher(covered, Default::default());
+        for (id, (row, item)) in items.iter().enumerate() {
+            for col in columns(item) {
+                cells.insert((*row, col), id);
+            }
+        }
+
+        SchematicsGrid { items, cells }
+    }
+}
+
+impl FromStr for SchematicsGrid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(SchematicsGrid::parse(s, &SymbolSet::Any))
+    }
+}
+
+/// The columns an item covers.
+fn columns(item: &SchematicsSymbol) -> Range<usize> {
+    match item {
+        SchematicsSymbol::Number((_, span)) => span.clone(),
+        SchematicsSymbol::Symbol((_, position)) => *position..*position + 1,
+    }
+}
+
+impl SchematicsGrid {
+    /// The ids of the items touching item `id`, including diagonally, each once.
+    fn adjacent(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        let (row, item) = &self.items[id];
+        let columns = columns(item);
+
+        let rows = row.saturating_sub(1)..=row + 1;
+        let cols = columns.start.saturating_sub(1)..=columns.end;
+        rows.cartesian_product(cols)
+            .filter_map(|cell| self.cells.get(&cell).copied())
+            .filter(move |&other| other != id)
+            // An item's cells are next to each other on one row, so its repeats are consecutive.
+            .dedup()
+    }
+
+    // for numbers, finds neighboring symbols, for symbols neighbor numbers
+    pub fn neighbors(
+        &self,
+        line: usize,
+        symbol: &SchematicsSymbol,
+    ) -> HashSet<(usize, SchematicsSymbol)> {
+        // Only the item itself, not whatever else covers the cell it starts at.
+        let Some(id) = columns(symbol)
+            .next()
+            .and_then(|col| self.cells.get(&(line, col)))
+            .filter(|&&id| self.items[id].1 == *symbol)
+        else {
+            return HashSet::new();
+        };
+
+        self.adjacent(*id)
+            .map(|other| &self.items[other])
+            .filter(|(_, other)| symbol.is_number() != other.is_number())
+            .cloned()
+            .collect()
+    }
+
+    /// Every `symbol` with a number of adjacent part numbers in `neighbors`
+    /// (`2..=2` for the puzzle's gears, `3..` for busier ones), in reading order.
+    pub fn gears(
+        &self,
+        symbol: char,
+        neighbors: impl RangeBounds<usize> + 'static,
+    ) -> impl Iterator<Item = Gear> + '_ {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(move |(_, (_, item))| item.symbol() == Some(symbol))
+            .filter_map(move |(id, (row, item))| {
+                let numbers = self
+                    .adjacent(id)
+                    .filter_map(|other| self.items[other].1.number())
+                    .collect_vec();
+                neighbors.contains(&numbers.len()).then(|| Gear {
+                    symbol,
+                    row: *row,
+                    col: columns(item).start,
+                    numbers,
+                })
+            })
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<SchematicsGrid> {
+    s.parse()
+}
+
+pub fn part1(grid: &SchematicsGrid) -> Result<u64> {
+    // Going from the symbols is fewer probes than from the (longer) numbers.
+    let mut part_numbers = vec![false; grid.items.len()];
+    for (id, (_, item)) in grid.items.iter().enumerate() {
+        if item.is_symbol() {
+            for other in grid.adjacent(id) {
+                part_numbers[other] = true;
+            }
+        }
+    }
+
+    let sum = grid
+        .items
+        .iter()
+        .zip(part_numbers)
+        .filter(|(_, is_part)| *is_part)
+        .filter_map(|((_, item), _)| item.number())
+        .map(|n| n as u64)
+        .sum();
+    Ok(sum)
+}
+
+pub fn part2(grid: &SchematicsGrid) -> Result<u64> {
+    Ok(grid.gears('*', 2..=2).map(|gear| gear.ratio()).sum())
+}
+
+#[test]
+fn test() {
+    let input = textwrap::dedent(
+        "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..
+    .....144.-
+    ",
+    );
+
+    let input = SchematicsGrid::from_str(&input).unwrap();
+
+    assert_eq!(part1(&input).unwrap(), 4361);
+    assert_eq!(part2(&input).unwrap(), 467835);
+}
+
+#[test]
+fn test_neighbors() {
+    let input = textwrap::dedent(
+        "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    ",
+    );
+    let grid = SchematicsGrid::from_str(input.trim_start()).unwrap();
+
+    let star = SchematicsSymbol::Symbol(('*', 3));
+    let neighbors = grid.neighbors(1, &star);
+    assert_eq!(
+        neighbors,
+        HashSet::from([
+            (0, SchematicsSymbol::Number((467, 0..3))),
+            (2, SchematicsSymbol::Number((35, 2..4))),
+        ])
+    );
+
+    let number = SchematicsSymbol::Number((114, 5..8));
+    assert!(grid.neighbors(0, &number).is_empty());
+    // Not in the grid, even though 467 covers the same cells.
+    let number = SchematicsSymbol::Number((999, 0..3));
+    assert!(grid.neighbors(0, &number).is_empty());
+    let number = SchematicsSymbol::Number((467, 0..2));
+    assert!(grid.neighbors(0, &number).is_empty());
+    assert!(grid
+        .neighbors(1, &SchematicsSymbol::Symbol(('#', 3)))
+        .is_empty());
+    let number = SchematicsSymbol::Number((633, 6..9));
+    assert_eq!(
+        grid.neighbors(2, &number),
+        HashSet::from([(3, SchematicsSymbol::Symbol(('#', 6)))])
+    );
+}
+
+#[test]
+fn test_any_symbol() {
+    // `<`, `|` and `→` were all blank to the old hardcoded list.
+    let input = "12<...7|\n→..5....\n";
+    let grid: SchematicsGrid = input.parse().unwrap();
+
+    // The arrow is three bytes but one column, so 5 is still under `<`.
+    assert_eq!(part1(&grid).unwrap(), 12 + 7 + 5);
+    let arrow = SchematicsSymbol::Symbol(('→', 0));
+    assert_eq!(
+        grid.neighbors(1, &arrow),
+        HashSet::from([(0, SchematicsSymbol::Number((12, 0..2)))])
+    );
+
+    let grid = SchematicsGrid::parse(input, &SymbolSet::only("<|→"));
+    assert_eq!(part1(&grid).unwrap(), 12 + 7 + 5);
+
+    let unexpected = validate(input, &SymbolSet::only("<|"));
+    assert_eq!(
+        unexpected,
+        vec![UnexpectedGlyph {
+            row: 1,
+            col: 0,
+            glyph: '→'
+        }]
+    );
+    assert_eq!(unexpected[0].to_string(), "'→' at line 2, column 1");
+    // Outside the set the arrow is blank, and 12 no longer has it as a neighbour.
+    let grid = SchematicsGrid::parse(input, &SymbolSet::only("<|"));
+    assert!(grid
+        .items
+        .iter()
+        .all(|(_, item)| item.symbol() != Some('→')));
+    assert!(grid.neighbors(1, &arrow).is_empty());
+    assert_eq!(
+        grid.neighbors(0, &SchematicsSymbol::Number((12, 0..2))),
+        HashSet::from([(0, SchematicsSymbol::Symbol(('<', 2)))])
+    );
+    assert_eq!(part1(&grid).unwrap(), 12 + 7 + 5);
+
+    assert_eq!(
+        validate("1 2\t.\n", &SymbolSet::Any),
+        vec![
+            UnexpectedGlyph {
+                row: 0,
+                col: 1,
+                glyph: ' '
+            },
+            UnexpectedGlyph {
+                row: 0,
+                col: 3,
+                glyph: '\t'
+            },
+        ]
+    );
+    // Whitespace and control characters are blank, so the numbers don't touch anything.
+    let grid: SchematicsGrid = "1 2\t3\n\u{7}4\r.\n".parse().unwrap();
+    assert!(grid.items.iter().all(|(_, item)| item.is_number()));
+    assert_eq!(part1(&grid).unwrap(), 0);
+}
+
+#[test]
+fn test_gears() {
+    let input = textwrap::dedent(
+        "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..
+    ..2*3.....
+    ..4.5.....
+    ",
+    );
+    let grid: SchematicsGrid = input.trim_start().parse().unwrap();
+
+    let gears = grid.gears('*', 2..=2).collect_vec();
+    assert_eq!(
+        gears.iter().map(|g| g.numbers.clone()).collect_vec(),
+        vec![vec![467, 35], vec![755, 598]]
+    );
+    assert_eq!((gears[0].row, gears[0].col), (1, 3));
+    assert_eq!(gears[0].ratio(), 16345);
+
+    // The last `*` touches five numbers.
+    let busy = grid.gears('*', 3..).collect_vec();
+    assert_eq!(
+        busy,
+        vec![Gear {
+            symbol: '*',
+            row: 10,
+            col: 3,
+            numbers: vec![664, 2, 3, 4, 5],
+        }]
+    );
+
+    let lonely = grid
+        .gears('*', ..=1)
+        .map(|g| (g.row, g.numbers))
+        .collect_vec();
+    assert_eq!(lonely, vec![(4, vec![617])]);
+    assert_eq!(grid.gears('#', 1..=1).count(), 1);
+    assert_eq!(grid.gears('?', ..).count(), 0);
+
+    let number = &grid.items[0].1;
+    assert_eq!((number.number(), number.symbol()), (Some(467), None));
+}
+
+#[test]
+fn test_edges() {
+    // Numbers in every corner and along every edge, each touching a symbol only diagonally
+    // or through the border of the grid.
+    let input = "12...#34\n..*.....\n5......6\n.......$\n78.9.#..\n";
+    let grid: SchematicsGrid = input.parse().unwrap();
+
+    let spans = grid
+        .items
+        .iter()
+        .filter_map(|(row, item)| match item {
+            SchematicsSymbol::Number((n, span)) => Some((*row, *n, span.clone())),
+            SchematicsSymbol::Symbol(_) => None,
+        })
+        .collect_vec();
+    assert_eq!(
+        spans,
+        vec![
+            (0, 12, 0..2),
+            (0, 34, 6..8),
+            (2, 5, 0..1),
+            (2, 6, 7..8),
+            (4, 78, 0..2),
+            (4, 9, 3..4),
+        ]
+    );
+    // 12 and 34 sit diagonally off `*` and `#`, 6 is above `$`, 78, 5 and 9 touch nothing.
+    assert_eq!(part1(&grid).unwrap(), 12 + 34 + 6);
+    assert_eq!(
+        grid.neighbors(0, &SchematicsSymbol::Number((34, 6..8))),
+        HashSet::from([(0, SchematicsSymbol::Symbol(('#', 5)))])
+    );
+    assert!(grid
+        .neighbors(4, &SchematicsSymbol::Number((78, 0..2)))
+        .is_empty());
+}
+
+#[test]
+fn test_several_symbols() {
+    // 123 touches four symbols but is one part number; each symbol still sees it.
+    let input = "*...#\n.123.\n$..%.\n";
+    let grid: SchematicsGrid = input.parse().unwrap();
+
+    assert_eq!(part1(&grid).unwrap(), 123);
+    let number = SchematicsSymbol::Number((123, 1..4));
+    assert_eq!(grid.neighbors(1, &number).len(), 4);
+    for symbol in ['*', '#', '$', '%'] {
+        let gears = grid.gears(symbol, ..).collect_vec();
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].numbers, vec![123]);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A number read straight off the characters: its value, row and columns,
+    /// and the symbols in the 8 cells around each of its digits.
+    struct Run {
+        value: usize,
+        row: usize,
+        span: Range<usize>,
+        symbols: HashSet<(usize, usize)>,
+    }
+
+    fn brute_force(rows: &[Vec<char>]) -> Vec<Run> {
+        let is_symbol = |row: usize, col: usize| {
+            rows.get(row)
+                .and_then(|line| line.get(col))
+                .is_some_and(|&c| !c.is_ascii_digit() && c != '.')
+        };
+
+        let mut runs = vec![];
+        for (row, line) in rows.iter().enumerate() {
+            let mut col = 0;
+            while col < line.len() {
+                if !line[col].is_ascii_digit() {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < line.len() && line[col].is_ascii_digit() {
+                    col += 1;
+                }
+
+                let mut symbols = HashSet::new();
+                for digit in start..col {
+                    for (dr, dc) in (-1..=1).cartesian_product(-1..=1) {
+                        let (Some(r), Some(c)) =
+                            (row.checked_add_signed(dr), digit.checked_add_signed(dc))
+                        else {
+                            continue;
+                        };
+                        if is_symbol(r, c) {
+                            symbols.insert((r, c));
+                        }
+                    }
+                }
+                runs.push(Run {
+                    value: line[start..col].iter().collect::<String>().parse().unwrap(),
+                    row,
+                    span: start..col,
+                    symbols,
+                });
+            }
+        }
+        runs
+    }
+
+    fn grids() -> impl Strategy<Value = Vec<Vec<char>>> {
+        let cell = prop_oneof![
+            4 => Just('.'),
+            4 => prop::char::range('0', '9'),
+            2 => prop::sample::select(vec!['*', '#', '$', '→']),
+        ];
+        (1usize..12).prop_flat_map(move |cols| {
+            prop::collection::vec(prop::collection::vec(cell.clone(), cols), 1..8)
+        })
+    }
+
+    fn render(rows: &[Vec<char>]) -> String {
+        rows.iter()
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn part_numbers_match_brute_force(rows in grids()) {
+            let grid: SchematicsGrid = render(&rows).parse().unwrap();
+            let runs = brute_force(&rows);
+
+            let expected: u64 = runs
+                .iter()
+                .filter(|run| !run.symbols.is_empty())
+                .map(|run| run.value as u64)
+                .sum();
+            prop_assert_eq!(part1(&grid).unwrap(), expected);
+        }
+
+        #[test]
+        fn neighbors_match_brute_force(rows in grids()) {
+            let grid: SchematicsGrid = render(&rows).parse().unwrap();
+
+            for run in brute_force(&rows) {
+                let number = SchematicsSymbol::Number((run.value, run.span.clone()));
+                let symbols = grid
+                    .neighbors(run.row, &number)
+                    .into_iter()
+                    .map(|(row, symbol)| (row, columns(&symbol).start))
+                    .collect::<HashSet<_>>();
+                prop_assert_eq!(symbols, run.symbols);
+            }
+        }
+
+        #[test]
+        fn gears_match_brute_force(rows in grids()) {
+            let grid: SchematicsGrid = render(&rows).parse().unwrap();
+            let runs = brute_force(&rows);
+
+            for symbol in ['*', '#', '$', '→'] {
+                for gear in grid.gears(symbol, ..) {
+                    prop_assert_eq!(rows[gear.row][gear.col], symbol);
+                    // The runs are in reading order, like the gear's numbers.
+                    let expected = runs
+                        .iter()
+                        .filter(|run| run.symbols.contains(&(gear.row, gear.col)))
+                        .map(|run| run.value)
+                        .collect_vec();
+                    prop_assert_eq!(gear.numbers, expected);
+                }
+            }
+            let symbols = rows.iter().flatten().filter(|c| !c.is_ascii_digit() && **c != '.');
+            prop_assert_eq!(grid.gears('*', ..).count()
+                + grid.gears('#', ..).count()
+                + grid.gears('$', ..).count()
+                + grid.gears('→', ..).count(), symbols.count());
+        }
+    }
+}
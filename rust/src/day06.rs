@@ -0,0 +1,119 @@
+use crate::regex;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use roots::{find_roots_quadratic, Roots};
+
+pub type Input = Vec<(i64, i64)>;
+
+/// x * y > distance
+/// x + y = time
+/// solve for x
+pub fn get_num_ways_to_win(time: i64, distance: i64) -> Option<i64> {
+    if let Roots::Two([start, stop]) = find_roots_quadratic(1.0, -time as f64, distance as f64) {
+        Some((stop.ceil() - start.floor()) as i64 - 1)
+    } else {
+        None
+    }
+}
+
+/// Reference for [`get_num_ways_to_win`], tries every hold time.
+pub fn get_num_ways_to_win_naive(time: i64, distance: i64) -> Option<i64> {
+    let ways = (0..=time)
+        .filter(|hold| hold * (time - hold) > distance)
+        .count() as i64;
+    (ways > 0).then_some(ways)
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    let mut result = 1;
+    for problem in input {
+        let (time, distance) = (problem.0, problem.1);
+
+        result *= get_num_ways_to_win(time, distance).context("problem is unsolvable")?;
+    }
+
+    Ok(result)
+}
+
+/// The races of part 2 are really a single race, with the kerning removed.
+pub fn merged_race(input: &Input) -> Result<(i64, i64)> {
+    let mut merged_time = String::new();
+    let mut merged_distance = String::new();
+
+    for problem in input {
+        let (time, distance) = (problem.0, problem.1);
+        merged_time += &format!("{}", time);
+        merged_distance += &format!("{}", distance);
+    }
+
+    Ok((
+        merged_time.parse().context("expected at least one race")?,
+        merged_distance
+            .parse()
+            .context("expected at least one race")?,
+    ))
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    let (time, distance) = merged_race(input)?;
+    get_num_ways_to_win(time, distance).context("problem is unsolvable")
+}
+
+pub fn part1_naive(input: &Input) -> Result<i64> {
+    let mut result = 1;
+    for &(time, distance) in input {
+        result *= get_num_ways_to_win_naive(time, distance).context("problem is unsolvable")?;
+    }
+
+    Ok(result)
+}
+
+pub fn part2_naive(input: &Input) -> Result<i64> {
+    let (time, distance) = merged_race(input)?;
+    get_num_ways_to_win_naive(time, distance).context("problem is unsolvable")
+}
+
+// Time:      7  15   30
+// Distance:  9  40  200
+pub fn parse_input(input: &str) -> Result<Input> {
+    let numbers = regex!(r"(\d+)");
+    let mut lines = input.lines();
+
+    let time = lines
+        .next()
+        .map(|line| {
+            numbers
+                .captures_iter(line)
+                .map(|n| n[0].parse::<i64>().expect("regex matches number"))
+                .collect_vec()
+        })
+        .context("expected non empty input")?;
+
+    let distance = lines
+        .next()
+        .map(|line| {
+            numbers
+                .captures_iter(line)
+                .map(|n| n[0].parse::<i64>().expect("regex matches number"))
+                .collect_vec()
+        })
+        .context("expected input to have 2 lines")?;
+
+    anyhow::ensure!(lines.next().is_none(), "Expected exactly 2 lines");
+
+    Ok(time.into_iter().zip(distance).collect_vec())
+}
+
+#[test]
+fn test() {
+    let input_str = textwrap::dedent(
+        "Time:      7  15   30
+         Distance:  9  40  200",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part1(&input).unwrap(), 288);
+    assert_eq!(part2(&input).unwrap(), 71503);
+    assert_eq!(part1_naive(&input).unwrap(), 288);
+    assert_eq!(part2_naive(&input).unwrap(), 71503);
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::math::lcm_all;
+use crate::regex;
+use anyhow::{bail, Context, Result};
+
+pub type Instructions = String;
+pub type Graph = HashMap<String, Vec<String>>;
+pub type Input = (Instructions, Graph);
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    let mut lines = s.lines();
+    let instructions = lines.next().context("expected input to be not empty")?;
+    lines.next().context("expected blank line")?; // skip newline
+
+    //AAA = (BBB, CCC)
+    let re = regex!(r"(?P<node>\w{3}) = \((?P<left>\w{3}),\s(?P<right>\w{3})\)");
+
+    let mut graph = Graph::new();
+
+    for line in lines {
+        let capture = re.captures(line.trim()).context("invalid input")?;
+        let node = capture["node"].to_string();
+        let left = capture["left"].to_string();
+        let right = capture["right"].to_string();
+
+        let e = graph.entry(node).or_default();
+        e.push(left);
+        e.push(right);
+    }
+
+    Ok((instructions.to_string(), graph))
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    path_len_from(&input.1, &input.0, "AAA")
+}
+
+// Finds path len to a node terminating at "Z", starting from some node.
+pub fn path_len_from(graph: &Graph, instructions: &str, node: &str) -> Result<i64> {
+    let mut steps = 0;
+    let mut at = node;
+
+    for inst in instructions.chars().cycle() {
+        match inst {
+            'L' => at = &graph[at][0],
+            'R' => at = &graph[at][1],
+            _ => bail!("Unexpected instruction"),
+        }
+        steps += 1;
+        if at.ends_with("Z") {
+            break;
+        }
+    }
+
+    Ok(steps)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    let instuctions = &input.0;
+    let graph = &input.1;
+
+    // Start at all nodes that end with "A"
+    let at = graph
+        .keys()
+        .filter(|node| node.ends_with("A"))
+        .cloned()
+        .collect::<Vec<String>>();
+
+    // Each ghost loops back to its start after reaching `..Z`, so they all meet
+    // at the least common multiple of their path lengths.
+    let path_lens = at
+        .iter()
+        .map(|node| path_len_from(graph, instuctions, node))
+        .collect::<Result<Vec<_>>>()?;
+
+    lcm_all(path_lens).context("expected at least one starting node")
+}
+
+/// Reference for [`part2`], walks all the ghosts together until they're all on a "Z" node.
+/// Gives up after `max_steps`, since that's only reachable for small inputs.
+pub fn part2_naive(input: &Input, max_steps: i64) -> Result<i64> {
+    let (instructions, graph) = input;
+    let mut at = graph
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .map(|node| node.as_str())
+        .collect::<Vec<&str>>();
+
+    for (steps, inst) in (1..=max_steps).zip(instructions.chars().cycle()) {
+        let side = match inst {
+            'L' => 0,
+            'R' => 1,
+            _ => bail!("Unexpected instruction"),
+        };
+
+        for node in at.iter_mut() {
+            *node = &graph.get(*node).context("unknown node")?[side];
+        }
+
+        if at.iter().all(|node| node.ends_with('Z')) {
+            return Ok(steps);
+        }
+    }
+
+    bail!("ghosts did not meet within {} steps", max_steps)
+}
+
+#[test]
+fn test_no_cycle() {
+    let input_str = textwrap::dedent(
+        "RL
+
+        AAA = (BBB, CCC)
+        BBB = (DDD, EEE)
+        CCC = (ZZZ, GGG)
+        DDD = (DDD, DDD)
+        EEE = (EEE, EEE)
+        GGG = (GGG, GGG)
+        ZZZ = (ZZZ, ZZZ)",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part1(&input).unwrap(), 2);
+}
+
+#[test]
+fn test_cycle() {
+    let input_str = textwrap::dedent(
+        "LLR
+
+        AAA = (BBB, BBB)
+        BBB = (AAA, ZZZ)
+        ZZZ = (ZZZ, ZZZ)",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part1(&input).unwrap(), 6);
+}
+
+#[test]
+fn test_part2() {
+    let input_str = textwrap::dedent(
+        "LR
+
+        11A = (11B, XXX)
+        11B = (XXX, 11Z)
+        11Z = (11B, XXX)
+        22A = (22B, XXX)
+        22B = (22C, 22C)
+        22C = (22Z, 22Z)
+        22Z = (22B, 22B)
+        XXX = (XXX, XXX)",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part2(&input).unwrap(), 6);
+    assert_eq!(part2_naive(&input, 100).unwrap(), 6);
+}
+
+#[test]
+fn test_part2_shared_factors() {
+    // Loops of 4 and 6 share a factor of 2, so the ghosts meet after 12 steps, not 24.
+    let input_str = textwrap::dedent(
+        "L
+
+        11A = (11B, XXX)
+        11B = (11C, XXX)
+        11C = (11D, XXX)
+        11D = (11Z, XXX)
+        11Z = (11B, XXX)
+        22A = (22B, XXX)
+        22B = (22C, XXX)
+        22C = (22D, XXX)
+        22D = (22E, XXX)
+        22E = (22F, XXX)
+        22F = (22Z, XXX)
+        22Z = (22B, XXX)
+        XXX = (XXX, XXX)",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part2(&input).unwrap(), 12);
+    assert_eq!(part2_naive(&input, 100).unwrap(), 12);
+}
@@ -0,0 +1,225 @@
+use crate::math::find_cycle;
+use crate::trace::{Event, NoTrace, Tracer};
+use crate::Direction;
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+pub type Input = Grid;
+
+#[derive(Clone)]
+pub struct Grid {
+    pub inner: grid::Grid<char>,
+}
+
+impl FromStr for Grid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let mut grid: Vec<Vec<char>> = vec![];
+
+        for line in s.lines() {
+            let line = line.trim();
+            grid.push(line.chars().collect());
+        }
+
+        let shape = (grid.len(), grid[0].len());
+        let chars = grid.clone().into_iter().flatten().collect::<Vec<_>>();
+        let grid_arr = grid::Grid::from_vec(chars, shape.1);
+
+        Ok(Grid { inner: grid_arr })
+    }
+}
+
+impl Grid {
+    pub fn tilt_north(&mut self) {
+        // skip first row
+        loop {
+            let mut rocks_moved = 0;
+            for i in 1..self.inner.rows() {
+                for j in 0..self.inner.cols() {
+                    let tile = self.inner[(i, j)];
+
+                    if tile == 'O' {
+                        let north_tile = self.inner[(i - 1, j)];
+
+                        if north_tile == '.' {
+                            self.inner[(i, j)] = '.';
+                            self.inner[(i - 1, j)] = 'O';
+                            rocks_moved += 1;
+                        }
+                    }
+                }
+            }
+            if rocks_moved == 0 {
+                break;
+            }
+        }
+    }
+
+    pub fn tilt_west(&mut self) {
+        self.inner.rotate_right();
+        self.tilt_north();
+        self.inner.rotate_left();
+    }
+
+    pub fn tilt_east(&mut self) {
+        self.inner.rotate_left();
+        self.tilt_north();
+        self.inner.rotate_right();
+    }
+
+    pub fn tilt_south(&mut self) {
+        self.inner.rotate_half();
+        self.tilt_north();
+        self.inner.rotate_half();
+    }
+
+    pub fn tilt(&mut self, direction: Direction) {
+        match direction {
+            Direction::Up => self.tilt_north(),
+            Direction::Left => self.tilt_west(),
+            Direction::Down => self.tilt_south(),
+            Direction::Right => self.tilt_east(),
+        }
+    }
+
+    pub fn cycle(&mut self, tracer: &mut dyn Tracer) {
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            self.tilt(direction);
+            if tracer.enabled() {
+                tracer.event(Event::Tilt {
+                    direction,
+                    load: self.load(),
+                });
+            }
+        }
+    }
+
+    pub fn load(&self) -> i64 {
+        let mut load = 0;
+        for i in 0..self.inner.rows() {
+            for j in 0..self.inner.cols() {
+                if self.inner[(i, j)] == 'O' {
+                    load += self.inner.rows() - i
+                }
+            }
+        }
+        load as i64
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Input> {
+    Grid::from_str(input)
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    part1_traced(input, &mut NoTrace)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    part2_traced(input, &mut NoTrace)
+}
+
+pub fn part1_traced(input: &Input, tracer: &mut dyn Tracer) -> Result<i64> {
+    let mut g: Grid = input.clone();
+
+    g.tilt_north();
+    let load = g.load();
+    tracer.event(Event::Tilt {
+        direction: Direction::Up,
+        load,
+    });
+
+    Ok(load)
+}
+
+pub fn part2_traced(input: &Input, tracer: &mut dyn Tracer) -> Result<i64> {
+    load_after_cycles(input, 1_000_000_000, tracer)
+}
+
+/// Load on the north beams after `n` spin cycles, skipping ahead once the platform repeats.
+pub fn load_after_cycles(input: &Input, n: usize, tracer: &mut dyn Tracer) -> Result<i64> {
+    let mut array = input.clone();
+    // The load after each number of spin cycles so far.
+    let mut loads = vec![];
+
+    let states = (0..=n).map(|i| {
+        if i > 0 {
+            array.cycle(tracer);
+        }
+        let load = array.load();
+        if i > 0 {
+            tracer.event(Event::SpinCycle { cycle: i, load });
+        }
+        loads.push(load);
+        array.inner.iter().copied().collect::<Vec<char>>()
+    });
+
+    match find_cycle(states) {
+        Some(cycle) => {
+            tracer.event(Event::CycleFound {
+                start: cycle.start,
+                length: cycle.length,
+            });
+            loads
+                .get(cycle.index_of(n))
+                .copied()
+                .context("index is within the cycle")
+        }
+        // Done before the platform started repeating.
+        None => Ok(array.load()),
+    }
+}
+
+/// Reference for [`load_after_cycles`], actually spins `n` times.
+pub fn load_after_cycles_naive(input: &Input, n: usize) -> i64 {
+    let mut array = input.clone();
+    for _ in 0..n {
+        array.cycle(&mut NoTrace);
+    }
+    array.load()
+}
+
+#[test]
+fn test() {
+    let input_str = textwrap::dedent(
+        "O....#....
+        O.OO#....#
+        .....##...
+        OO.#O....O
+        .O.....O#.
+        O.#..O.#.#
+        ..O..#O..O
+        .......O..
+        #....###..
+        #OO..#....",
+    );
+
+    let input = parse_input(&input_str).unwrap();
+    assert_eq!(part1(&input).unwrap(), 136);
+    assert_eq!(part2(&input).unwrap(), 64);
+    assert_eq!(load_after_cycles(&input, 3, &mut NoTrace).unwrap(), 69);
+    assert_eq!(load_after_cycles_naive(&input, 3), 69);
+
+    let mut events = vec![];
+    part2_traced(&input, &mut events).unwrap();
+    assert_eq!(
+        events.last(),
+        Some(&Event::CycleFound {
+            start: 3,
+            length: 7
+        })
+    );
+    assert_eq!(
+        events
+            .iter()
+            .filter(|e| matches!(e, Event::SpinCycle { .. }))
+            .count(),
+        10
+    );
+}
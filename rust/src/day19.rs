@@ -0,0 +1,380 @@
+use std::{collections::HashMap, ops::Range, str::FromStr};
+
+use crate::regex;
+use anyhow::{bail, Context, Result};
+
+pub type Input = System;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn index(&self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        Ok(match s {
+            "x" => Category::X,
+            "m" => Category::M,
+            "a" => Category::A,
+            "s" => Category::S,
+            _ => bail!("Invalid category `{}`", s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl From<&str> for Target {
+    fn from(s: &str) -> Self {
+        match s {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Condition {
+    LessThan(Category, i64),
+    GreaterThan(Category, i64),
+}
+
+impl Condition {
+    fn matches(&self, part: &Part) -> bool {
+        match self {
+            Condition::LessThan(category, value) => part.0[category.index()] < *value,
+            Condition::GreaterThan(category, value) => part.0[category.index()] > *value,
+        }
+    }
+
+    /// Splits a range of ratings into the (matching, not matching) sub ranges.
+    fn split(&self, ratings: &RatingRanges) -> (Option<RatingRanges>, Option<RatingRanges>) {
+        let (category, matching, rest) = match self {
+            Condition::LessThan(category, value) => {
+                let r = &ratings.0[category.index()];
+                (
+                    category,
+                    r.start..(*value).min(r.end),
+                    (*value).max(r.start)..r.end,
+                )
+            }
+            Condition::GreaterThan(category, value) => {
+                let r = &ratings.0[category.index()];
+                (
+                    category,
+                    (*value + 1).max(r.start)..r.end,
+                    r.start..(*value + 1).min(r.end),
+                )
+            }
+        };
+
+        let with = |range: Range<i64>| {
+            if range.is_empty() {
+                None
+            } else {
+                let mut ratings = ratings.clone();
+                ratings.0[category.index()] = range;
+                Some(ratings)
+            }
+        };
+
+        (with(matching), with(rest))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+impl FromStr for Rule {
+    type Err = anyhow::Error;
+
+    // a<2006:qkq or rfg
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let re = regex!(r"^(?P<category>[xmas])(?P<op>[<>])(?P<value>\d+):(?P<target>\w+)$");
+
+        if let Some(cap) = re.captures(s) {
+            let category = Category::from_str(&cap["category"])?;
+            let value = cap["value"].parse().context("expected number")?;
+            let condition = match &cap["op"] {
+                "<" => Condition::LessThan(category, value),
+                ">" => Condition::GreaterThan(category, value),
+                _ => unreachable!("regex cannot match this"),
+            };
+
+            Ok(Rule {
+                condition: Some(condition),
+                target: Target::from(&cap["target"]),
+            })
+        } else if !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(Rule {
+                condition: None,
+                target: Target::from(s),
+            })
+        } else {
+            bail!("Invalid rule `{}`", s)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part([i64; 4]);
+
+impl Part {
+    fn rating(&self) -> i64 {
+        self.0.iter().sum()
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    // {x=787,m=2655,a=1222,s=2876}
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let re = regex!(r"^\{x=(\d+),m=(\d+),a=(\d+),s=(\d+)\}$");
+        let cap = re
+            .captures(s)
+            .with_context(|| format!("Invalid part `{}`", s))?;
+
+        let mut ratings = [0; 4];
+        for (i, rating) in ratings.iter_mut().enumerate() {
+            *rating = cap[i + 1].parse().context("expected number")?;
+        }
+
+        Ok(Part(ratings))
+    }
+}
+
+/// Half open ranges for each of the `xmas` categories.
+#[derive(Debug, Clone)]
+pub struct RatingRanges([Range<i64>; 4]);
+
+impl RatingRanges {
+    fn combinations(&self) -> i64 {
+        self.0.iter().map(|r| r.end - r.start).product()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct System {
+    workflows: HashMap<String, Vec<Rule>>,
+    parts: Vec<Part>,
+}
+
+impl FromStr for System {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let re = regex!(r"^(?P<name>\w+)\{(?P<rules>[^}]+)\}$");
+
+        let mut workflows = HashMap::new();
+        let mut parts = vec![];
+
+        for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if line.starts_with('{') {
+                parts.push(Part::from_str(line)?);
+                continue;
+            }
+
+            let cap = re
+                .captures(line)
+                .with_context(|| format!("Invalid workflow `{}`", line))?;
+
+            let rules = cap["rules"]
+                .split(',')
+                .map(Rule::from_str)
+                .collect::<Result<Vec<_>>>()?;
+
+            workflows.insert(cap["name"].to_string(), rules);
+        }
+
+        anyhow::ensure!(workflows.contains_key("in"), "expected an `in` workflow");
+
+        Ok(System { workflows, parts })
+    }
+}
+
+impl System {
+    fn workflow(&self, name: &str) -> Result<&[Rule]> {
+        self.workflows
+            .get(name)
+            .map(|rules| rules.as_slice())
+            .with_context(|| format!("Unknown workflow `{}`", name))
+    }
+
+    pub fn is_accepted(&self, part: &Part) -> Result<bool> {
+        let mut at = "in";
+        let mut path = vec![];
+
+        loop {
+            if path.contains(&at) {
+                bail!("Workflows loop: {} -> {}", path.join(" -> "), at);
+            }
+            path.push(at);
+
+            let rule = self
+                .workflow(at)?
+                .iter()
+                .find(|rule| rule.condition.map_or(true, |c| c.matches(part)))
+                .with_context(|| format!("No rule in `{}` matched {:?}", at, part))?;
+
+            match &rule.target {
+                Target::Accept => return Ok(true),
+                Target::Reject => return Ok(false),
+                Target::Workflow(name) => at = name,
+            }
+        }
+    }
+
+    /// Pushes the whole `1..=4000` hyper-rectangle through the workflows, splitting it on every
+    /// condition, and sums the volume of every piece that reaches `A`.
+    ///
+    /// A piece that comes back to a workflow it already went through would go around forever,
+    /// since it meets the same conditions every time, so that's an error.
+    pub fn accepted_combinations(&self) -> Result<i64> {
+        let mut accepted = 0;
+        let mut queue = vec![(
+            "in",
+            RatingRanges([1..4001, 1..4001, 1..4001, 1..4001]),
+            vec![],
+        )];
+
+        while let Some((at, ratings, mut path)) = queue.pop() {
+            if path.contains(&at) {
+                bail!("Workflows loop: {} -> {}", path.join(" -> "), at);
+            }
+            path.push(at);
+
+            let mut remaining = Some(ratings);
+
+            for rule in self.workflow(at)? {
+                let Some(current) = remaining.take() else {
+                    break;
+                };
+
+                let (matching, rest) = match rule.condition {
+                    Some(condition) => condition.split(&current),
+                    None => (Some(current), None),
+                };
+
+                if let Some(matching) = matching {
+                    match &rule.target {
+                        Target::Accept => accepted += matching.combinations(),
+                        Target::Reject => {}
+                        Target::Workflow(name) => {
+                            queue.push((name.as_str(), matching, path.clone()))
+                        }
+                    }
+                }
+
+                remaining = rest;
+            }
+        }
+
+        Ok(accepted)
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    System::from_str(s)
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    let mut sum = 0;
+    for part in &input.parts {
+        if input.is_accepted(part)? {
+            sum += part.rating();
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    input.accepted_combinations()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "px{a<2006:qkq,m>2090:A,rfg}
+            pv{a>1716:R,A}
+            lnx{m>1548:A,A}
+            rfg{s<537:gd,x>2440:R,A}
+            qs{s>3448:A,lnx}
+            qkq{x<1416:A,crn}
+            crn{x>2662:A,R}
+            in{s<1351:px,qqz}
+            qqz{s>2770:qs,m<1801:hdj,R}
+            gd{a>3333:R,R}
+            hdj{m>838:A,pv}
+
+            {x=787,m=2655,a=1222,s=2876}
+            {x=1679,m=44,a=2067,s=496}
+            {x=2036,m=264,a=79,s=2244}
+            {x=2461,m=1339,a=466,s=291}
+            {x=2127,m=1623,a=2188,s=1013}
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 19114);
+        assert_eq!(part2(&input).unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn test_split() {
+        let ratings = RatingRanges([1..4001, 1..4001, 1..4001, 1..4001]);
+
+        let (matching, rest) = Condition::LessThan(Category::A, 2006).split(&ratings);
+        assert_eq!(matching.unwrap().0[2], 1..2006);
+        assert_eq!(rest.unwrap().0[2], 2006..4001);
+
+        let (matching, rest) = Condition::GreaterThan(Category::M, 4000).split(&ratings);
+        assert!(matching.is_none());
+        assert_eq!(rest.unwrap().0[1], 1..4001);
+    }
+
+    #[test]
+    fn test_loop() {
+        let input_str = "in{x>1:a,R}\na{x>1:b,R}\nb{a}\n\n{x=5,m=1,a=1,s=1}\n";
+        let input = parse_input(input_str).unwrap();
+        let error = part1(&input).unwrap_err();
+        assert_eq!(error.to_string(), "Workflows loop: in -> a -> b -> a");
+        assert!(part2(&input).is_err());
+
+        // Reaching a workflow along two different paths is fine.
+        let input_str = "in{x>1:a,b}\na{c}\nb{c}\nc{A}\n\n{x=5,m=1,a=1,s=1}\n";
+        let input = parse_input(input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 8);
+        assert_eq!(part2(&input).unwrap(), 4000i64.pow(4));
+    }
+}
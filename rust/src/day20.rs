@@ -0,0 +1,382 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use crate::math::{find_cycle, lcm_all};
+use anyhow::{bail, Context, Result};
+use petgraph::{
+    dot::{Config, Dot},
+    graph::DiGraph,
+};
+
+pub type Input = Configuration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleKind {
+    Broadcaster,
+    // '%', starts off
+    FlipFlop { on: bool },
+    // '&', remembers the last pulse from each input, defaulting to low
+    Conjunction { memory: HashMap<String, Pulse> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    name: String,
+    kind: ModuleKind,
+    outputs: Vec<String>,
+}
+
+impl Module {
+    /// Handles a pulse from `from`, returning the pulse sent to all outputs (if any).
+    fn receive(&mut self, from: &str, pulse: Pulse) -> Option<Pulse> {
+        match &mut self.kind {
+            ModuleKind::Broadcaster => Some(pulse),
+            ModuleKind::FlipFlop { on } => match pulse {
+                Pulse::High => None,
+                Pulse::Low => {
+                    *on = !*on;
+                    Some(if *on { Pulse::High } else { Pulse::Low })
+                }
+            },
+            ModuleKind::Conjunction { memory } => {
+                memory.insert(from.to_string(), pulse);
+                if memory.values().all(|p| *p == Pulse::High) {
+                    Some(Pulse::Low)
+                } else {
+                    Some(Pulse::High)
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Module {
+    type Err = anyhow::Error;
+
+    // %a -> inv, con
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let (module, outputs) = s
+            .split_once(" -> ")
+            .with_context(|| format!("expected <module> -> <outputs>, got `{}`", s))?;
+
+        let (kind, name) = match module.chars().next() {
+            Some('%') => (ModuleKind::FlipFlop { on: false }, &module[1..]),
+            Some('&') => (
+                ModuleKind::Conjunction {
+                    memory: HashMap::new(),
+                },
+                &module[1..],
+            ),
+            _ if module == "broadcaster" => (ModuleKind::Broadcaster, module),
+            _ => bail!("Invalid module `{}`", module),
+        };
+
+        Ok(Module {
+            name: name.to_string(),
+            kind,
+            outputs: outputs.split(',').map(|o| o.trim().to_string()).collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Configuration {
+    modules: HashMap<String, Module>,
+}
+
+impl FromStr for Configuration {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let mut modules = s
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| Module::from_str(l).map(|m| (m.name.clone(), m)))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        anyhow::ensure!(
+            modules.contains_key("broadcaster"),
+            "expected a broadcaster module"
+        );
+
+        // Conjunctions need to know all of their inputs up front.
+        let edges = modules
+            .values()
+            .flat_map(|m| m.outputs.iter().map(|o| (m.name.clone(), o.clone())))
+            .collect::<Vec<_>>();
+
+        for (from, to) in edges {
+            if let Some(Module {
+                kind: ModuleKind::Conjunction { memory },
+                ..
+            }) = modules.get_mut(&to)
+            {
+                memory.insert(from, Pulse::Low);
+            }
+        }
+
+        Ok(Configuration { modules })
+    }
+}
+
+impl Configuration {
+    /// Presses the button once, calling `on_pulse` for every pulse sent (including the button's).
+    fn press(&mut self, mut on_pulse: impl FnMut(&str, &str, Pulse)) {
+        let mut bus = VecDeque::new();
+        bus.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+
+        while let Some((from, to, pulse)) = bus.pop_front() {
+            on_pulse(&from, &to, pulse);
+
+            // Untyped modules (like `output` or `rx`) only receive.
+            let Some(module) = self.modules.get_mut(&to) else {
+                continue;
+            };
+
+            if let Some(next) = module.receive(&from, pulse) {
+                for output in &module.outputs {
+                    bus.push_back((to.clone(), output.clone(), next));
+                }
+            }
+        }
+    }
+
+    /// Number of (low, high) pulses sent over `presses` button presses.
+    pub fn count_pulses(&mut self, presses: usize) -> (i64, i64) {
+        let (mut low, mut high) = (0, 0);
+
+        for _ in 0..presses {
+            self.press(|_, _, pulse| match pulse {
+                Pulse::Low => low += 1,
+                Pulse::High => high += 1,
+            });
+        }
+
+        (low, high)
+    }
+
+    fn inputs_of(&self, name: &str) -> Vec<String> {
+        let mut inputs = self
+            .modules
+            .values()
+            .filter(|m| m.outputs.iter().any(|o| o == name))
+            .map(|m| m.name.clone())
+            .collect::<Vec<_>>();
+        inputs.sort();
+        inputs
+    }
+
+    /// `rx` is fed by a single conjunction, which only sends it a low pulse when all of its inputs
+    /// have sent it a high pulse during the same press. Each of those inputs does so periodically,
+    /// so the answer is the LCM of the periods.
+    pub fn presses_until_rx_low(&mut self, max_presses: usize) -> Result<i64> {
+        let feeders = self.inputs_of("rx");
+        let [hub] = feeders.as_slice() else {
+            bail!("expected `rx` to have exactly one input, got {:?}", feeders);
+        };
+
+        anyhow::ensure!(
+            matches!(self.modules[hub].kind, ModuleKind::Conjunction { .. }),
+            "expected `{}` to be a conjunction",
+            hub
+        );
+
+        let inputs = self.inputs_of(hub);
+        let mut hits: HashMap<String, Vec<usize>> = HashMap::new();
+
+        // A handful of hits per input is enough to see its period repeat.
+        const HITS: usize = 4;
+
+        for press in 1..=max_presses {
+            self.press(|from, to, pulse| {
+                if to == hub && pulse == Pulse::High {
+                    // An input can fire more than once in a press, that's still one hit.
+                    let hits = hits.entry(from.to_string()).or_default();
+                    if hits.last() != Some(&press) {
+                        hits.push(press);
+                    }
+                }
+            });
+
+            if inputs
+                .iter()
+                .all(|i| hits.get(i).is_some_and(|h| h.len() >= HITS))
+            {
+                break;
+            }
+        }
+
+        let mut periods = vec![];
+        for input in &inputs {
+            let hits = hits
+                .get(input)
+                .filter(|h| h.len() >= HITS)
+                .with_context(|| {
+                    format!("`{}` did not repeat in {} presses", input, max_presses)
+                })?;
+
+            let gaps = std::iter::once(hits[0])
+                .chain(hits.windows(2).map(|w| w[1] - w[0]))
+                .collect::<Vec<_>>();
+
+            let cycle = find_cycle(gaps.iter().copied())
+                .with_context(|| format!("`{}` did not settle into a cycle", input))?;
+
+            // LCM only works if every input fires at exact multiples of its period.
+            anyhow::ensure!(
+                cycle.start == 0 && cycle.length == 1,
+                "`{}` is not purely periodic: {:?}",
+                input,
+                gaps
+            );
+
+            periods.push(gaps[0] as i64);
+        }
+
+        lcm_all(periods).context("expected conjunction to have inputs")
+    }
+
+    pub fn to_graph(&self) -> DiGraph<String, ()> {
+        let mut graph = DiGraph::new();
+        let mut nodes = HashMap::new();
+
+        let mut names = self.modules.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            let module = &self.modules[&name];
+            let label = match module.kind {
+                ModuleKind::Broadcaster => name.clone(),
+                ModuleKind::FlipFlop { .. } => format!("%{}", name),
+                ModuleKind::Conjunction { .. } => format!("&{}", name),
+            };
+            nodes.insert(name, graph.add_node(label));
+        }
+
+        let mut names = self.modules.keys().collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            for output in &self.modules[name].outputs {
+                let to = *nodes
+                    .entry(output.clone())
+                    .or_insert_with(|| graph.add_node(output.clone()));
+                graph.add_edge(nodes[name], to, ());
+            }
+        }
+
+        graph
+    }
+
+    pub fn to_dot(&self) -> String {
+        let graph = self.to_graph();
+        let dot = Dot::with_attr_getters(
+            &graph,
+            &[Config::EdgeNoLabel, Config::NodeNoLabel],
+            &|_, _| String::new(),
+            &|_, (_, label)| format!("label = \"{}\"", label),
+        );
+        format!("{:?}", dot)
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    Configuration::from_str(s)
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    let (low, high) = input.clone().count_pulses(1000);
+    Ok(low * high)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    input.clone().presses_until_rx_low(100_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "broadcaster -> a, b, c
+            %a -> b
+            %b -> c
+            %c -> inv
+            &inv -> a
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 32000000);
+
+        let input_str = textwrap::dedent(
+            "broadcaster -> a
+            %a -> inv, con
+            &inv -> b
+            %b -> con
+            &con -> output
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 11687500);
+        assert!(input.to_dot().contains("label = \"&con\""));
+    }
+
+    #[test]
+    fn test_part2() {
+        // `c1` fires high every 2 presses, `c2` every 4 and `c3` every 8.
+        let input_str = textwrap::dedent(
+            "broadcaster -> f1, g1, h1
+            %f1 -> c1
+            &c1 -> hub
+            %g1 -> g2
+            %g2 -> c2
+            &c2 -> hub
+            %h1 -> h2
+            %h2 -> h3
+            %h3 -> c3
+            &c3 -> hub
+            &hub -> rx
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part2(&input).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_part2_repeated_hits() {
+        // `f1` turns on every 4 presses from press 2 and off every 4 from press 4. Turning on,
+        // both inverters send `c1` a low pulse, so it fires high twice in the same press.
+        // `c1` still fires high every 2 presses, and `c2` every 8.
+        let input_str = textwrap::dedent(
+            "broadcaster -> f0, g1
+            %f0 -> f1
+            %f1 -> b1, b2
+            &b1 -> c1
+            &b2 -> c1
+            &c1 -> hub
+            %g1 -> g2
+            %g2 -> g3
+            %g3 -> c2
+            &c2 -> hub
+            &hub -> rx
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part2(&input).unwrap(), 8);
+    }
+}
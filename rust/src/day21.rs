@@ -0,0 +1,197 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{math::extrapolate, parse_grid, CoordExt, GridExt};
+use anyhow::{bail, Context, Result};
+use grid::Grid;
+
+pub type Input = Garden;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    // '.' or 'S'
+    Plot,
+    // '#'
+    Rock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tiling {
+    // Walking off the map is not allowed
+    Bounded,
+    // The map repeats infinitely in every direction
+    Infinite,
+}
+
+#[derive(Clone)]
+pub struct Garden {
+    map: Grid<Tile>,
+    start: (i64, i64),
+}
+
+impl Garden {
+    fn tile(&self, coord: (i64, i64), tiling: Tiling) -> Option<&Tile> {
+        match tiling {
+            Tiling::Bounded => self.map.get_coordinate(coord),
+            Tiling::Infinite => self.map.get_coordinate_wrapping(coord),
+        }
+    }
+
+    /// BFS from the start, returns how many plots are first reached after each number of steps.
+    fn reached_per_step(&self, max_steps: usize, tiling: Tiling) -> Vec<i64> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        let mut reached = vec![0; max_steps + 1];
+
+        distances.insert(self.start, 0);
+        queue.push_back(self.start);
+
+        while let Some(coord) = queue.pop_front() {
+            let distance = distances[&coord];
+            reached[distance] += 1;
+
+            if distance == max_steps {
+                continue;
+            }
+
+            for next in [coord.up(), coord.down(), coord.left(), coord.right()] {
+                if self.tile(next, tiling) == Some(&Tile::Plot) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Number of plots the elf can end on after exactly each of `steps`.
+    /// The elf can always step back and forth, so a plot first reached after `d` steps is
+    /// reachable in exactly `n` steps if `d <= n` and both have the same parity.
+    pub fn reachable_counts(&self, steps: &[usize], tiling: Tiling) -> Vec<i64> {
+        let max_steps = steps.iter().copied().max().unwrap_or(0);
+
+        // by_parity[d] = plots first reached at d, d - 2, d - 4...
+        let mut by_parity = self.reached_per_step(max_steps, tiling);
+        for d in 2..by_parity.len() {
+            by_parity[d] += by_parity[d - 2];
+        }
+
+        steps.iter().map(|&n| by_parity[n]).collect()
+    }
+
+    pub fn reachable(&self, steps: usize, tiling: Tiling) -> i64 {
+        self.reachable_counts(&[steps], tiling)[0]
+    }
+
+    /// On an infinitely tiled map, the reachable count grows polynomially every time the walk
+    /// covers one more map width, so we sample a few widths and extrapolate from the
+    /// differences.
+    pub fn reachable_extrapolated(&self, steps: usize) -> Result<i64> {
+        let period = self.map.rows();
+        anyhow::ensure!(
+            period == self.map.cols(),
+            "expected a square map, got {}x{}",
+            self.map.rows(),
+            self.map.cols()
+        );
+
+        // One more sample than a quadratic needs, so we can see the differences settle.
+        const SAMPLES: usize = 4;
+
+        let offset = steps % period;
+        let samples = (0..SAMPLES)
+            .map(|k| offset + k * period)
+            .collect::<Vec<_>>();
+        let counts = self.reachable_counts(&samples, Tiling::Infinite);
+
+        extrapolate(&counts, (steps / period) as i64).with_context(|| {
+            format!(
+                "reachable counts {:?} are not a polynomial in the number of maps",
+                counts
+            )
+        })
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    let mut start = None;
+    let mut position = 0;
+
+    let map = parse_grid(s, |c| {
+        let tile = match c {
+            '.' => Tile::Plot,
+            '#' => Tile::Rock,
+            'S' => {
+                start = Some(position);
+                Tile::Plot
+            }
+            _ => bail!("Unknown tile `{}`", c),
+        };
+        position += 1;
+        Ok(tile)
+    })?;
+
+    let start = start.context("expected map to have a starting position")?;
+    let cols = map.cols();
+
+    Ok(Garden {
+        map,
+        start: ((start / cols) as i64, (start % cols) as i64),
+    })
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    Ok(input.reachable(64, Tiling::Bounded))
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    input.reachable_extrapolated(26501365)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "...........
+            .....###.#.
+            .###.##..#.
+            ..#.#...#..
+            ....#.#....
+            .##..S####.
+            .##..#...#.
+            .......##..
+            .##.#.####.
+            .##..##.##.
+            ...........
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(input.start, (5, 5));
+        assert_eq!(input.reachable(6, Tiling::Bounded), 16);
+        assert_eq!(
+            input.reachable_counts(&[6, 10, 50, 100], Tiling::Infinite),
+            vec![16, 50, 1594, 6536]
+        );
+    }
+
+    #[test]
+    fn test_extrapolated() {
+        // On an empty map we can reach a diamond of (n + 1)^2 plots.
+        let input_str = textwrap::dedent(
+            ".....
+            .....
+            ..S..
+            .....
+            .....
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(input.reachable_extrapolated(100).unwrap(), 101 * 101);
+        assert_eq!(input.reachable_extrapolated(102).unwrap(), 103 * 103);
+    }
+}
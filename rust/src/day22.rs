@@ -0,0 +1,198 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::regex;
+use anyhow::{Context, Result};
+use petgraph::{
+    algo::dominators::{simple_fast, Dominators},
+    graph::{DiGraph, NodeIndex},
+};
+
+pub type Input = Vec<Brick>;
+
+pub type Point = (i64, i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brick {
+    start: Point,
+    end: Point,
+}
+
+impl FromStr for Brick {
+    type Err = anyhow::Error;
+
+    // 1,0,1~1,2,1
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let re = regex!(r"^(\d+),(\d+),(\d+)~(\d+),(\d+),(\d+)$");
+        let cap = re
+            .captures(s)
+            .with_context(|| format!("Invalid brick `{}`", s))?;
+
+        let n = |i: usize| cap[i].parse::<i64>().context("expected number");
+        let (a, b) = ((n(1)?, n(2)?, n(3)?), (n(4)?, n(5)?, n(6)?));
+
+        // Normalize so that `start` is the lower corner on every axis.
+        Ok(Brick {
+            start: (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2)),
+            end: (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2)),
+        })
+    }
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (self.start.0..=self.end.0)
+            .flat_map(move |x| (self.start.1..=self.end.1).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> i64 {
+        self.end.2 - self.start.2 + 1
+    }
+}
+
+/// Who rests on whom once all the bricks have fallen.
+/// Edges go from a supporting brick to the bricks it supports, with the ground as the root.
+pub struct SupportGraph {
+    graph: DiGraph<Option<usize>, ()>,
+    ground: NodeIndex,
+    dominators: Dominators<NodeIndex>,
+}
+
+impl SupportGraph {
+    fn settle(bricks: &[Brick]) -> SupportGraph {
+        let mut graph = DiGraph::new();
+        let ground = graph.add_node(None);
+
+        let mut order = (0..bricks.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| bricks[i].start.2);
+
+        // (x, y) -> (top z, brick on top)
+        let mut height_map: HashMap<(i64, i64), (i64, NodeIndex)> = HashMap::new();
+
+        for i in order {
+            let brick = &bricks[i];
+            let node = graph.add_node(Some(i));
+
+            let below = brick
+                .footprint()
+                .map(|xy| height_map.get(&xy).copied().unwrap_or((0, ground)))
+                .collect::<Vec<_>>();
+
+            let rest_on = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+
+            let mut supporters = below
+                .into_iter()
+                .filter(|(z, _)| *z == rest_on)
+                .map(|(_, supporter)| supporter)
+                .collect::<Vec<_>>();
+            supporters.sort();
+            supporters.dedup();
+
+            for supporter in supporters {
+                graph.add_edge(supporter, node, ());
+            }
+
+            let top = rest_on + brick.height();
+            for xy in brick.footprint() {
+                height_map.insert(xy, (top, node));
+            }
+        }
+
+        let dominators = simple_fast(&graph, ground);
+
+        SupportGraph {
+            graph,
+            ground,
+            dominators,
+        }
+    }
+
+    /// For every brick, how many other bricks fall if it is removed.
+    /// A brick falls exactly when every path from the ground to it goes through the removed
+    /// brick, i.e. when the removed brick dominates it.
+    fn chain_reactions(&self) -> HashMap<usize, usize> {
+        let mut falls = HashMap::new();
+
+        for node in self.graph.node_indices() {
+            if let Some(brick) = self.graph[node] {
+                falls.entry(brick).or_insert(0);
+            }
+
+            let Some(strict_dominators) = self.dominators.strict_dominators(node) else {
+                continue;
+            };
+
+            for dominator in strict_dominators.filter(|d| *d != self.ground) {
+                let brick = self.graph[dominator].expect("only the ground has no brick");
+                *falls.entry(brick).or_insert(0) += 1;
+            }
+        }
+
+        falls
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    s.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(Brick::from_str)
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    let graph = SupportGraph::settle(input);
+    Ok(graph
+        .chain_reactions()
+        .values()
+        .filter(|&&falls| falls == 0)
+        .count() as i64)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    let graph = SupportGraph::settle(input);
+    Ok(graph.chain_reactions().values().sum::<usize>() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "1,0,1~1,2,1
+            0,0,2~2,0,2
+            0,2,3~2,2,3
+            0,0,4~0,2,4
+            2,0,5~2,2,5
+            0,1,6~2,1,6
+            1,1,8~1,1,9
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 5);
+        assert_eq!(part2(&input).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_chain_reactions() {
+        // A tower of three, with a fourth brick resting on both the tower and the ground.
+        let input_str = textwrap::dedent(
+            "0,0,1~0,0,1
+            0,0,2~0,0,2
+            0,0,3~0,0,3
+            1,0,1~1,0,3
+            0,0,4~1,0,4
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        let falls = SupportGraph::settle(&input).chain_reactions();
+        assert_eq!(falls[&0], 2);
+        assert_eq!(falls[&1], 1);
+        assert_eq!(falls[&2], 0);
+        assert_eq!(falls[&3], 0);
+        assert_eq!(falls[&4], 0);
+    }
+}
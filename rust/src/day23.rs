@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use crate::{parse_grid, CoordExt, Direction, GridExt};
+use anyhow::{Context, Result};
+use grid::Grid;
+
+pub type Input = Trails;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    // '.'
+    Path,
+    // '#'
+    Forest,
+    // '^', '>', 'v', '<'
+    Slope(Direction),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slopes {
+    // Once on a slope, the next step has to go downhill.
+    Slippery,
+    // Slopes are just paths.
+    Dry,
+}
+
+#[derive(Clone)]
+pub struct Trails {
+    map: Grid<Tile>,
+    start: (i64, i64),
+    end: (i64, i64),
+}
+
+/// Corridors collapsed into weighted edges between junctions (and the start and end).
+#[derive(Debug)]
+pub struct JunctionGraph {
+    // edges[from] = [(to, length)]
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl Trails {
+    fn is_open(&self, coord: (i64, i64)) -> bool {
+        matches!(
+            self.map.get_coordinate(coord),
+            Some(Tile::Path | Tile::Slope(_))
+        )
+    }
+
+    fn moves(&self, coord: (i64, i64), slopes: Slopes) -> Vec<(i64, i64)> {
+        let directions = match (self.map.get_coordinate(coord), slopes) {
+            (Some(Tile::Slope(direction)), Slopes::Slippery) => vec![*direction],
+            _ => Direction::ALL.to_vec(),
+        };
+
+        directions
+            .into_iter()
+            .map(|d| coord.step(d))
+            .filter(|next| self.is_open(*next))
+            .collect()
+    }
+
+    fn is_junction(&self, coord: (i64, i64)) -> bool {
+        coord == self.start
+            || coord == self.end
+            || (self.is_open(coord)
+                && Direction::ALL
+                    .iter()
+                    .filter(|d| self.is_open(coord.step(**d)))
+                    .count()
+                    > 2)
+    }
+
+    fn junction_graph(&self, slopes: Slopes) -> Result<JunctionGraph> {
+        let mut junctions = HashMap::new();
+        for i in 0..self.map.rows() as i64 {
+            for j in 0..self.map.cols() as i64 {
+                if self.is_junction((i, j)) {
+                    let index = junctions.len();
+                    junctions.insert((i, j), index);
+                }
+            }
+        }
+
+        anyhow::ensure!(
+            junctions.len() <= 64,
+            "expected at most 64 junctions, got {}",
+            junctions.len()
+        );
+
+        let mut edges = vec![vec![]; junctions.len()];
+
+        for (&junction, &from) in &junctions {
+            for first in self.moves(junction, slopes) {
+                // Walk the corridor until we hit the next junction (or a dead end).
+                let (mut previous, mut at, mut length) = (junction, first, 1);
+
+                let reached = loop {
+                    if let Some(&to) = junctions.get(&at) {
+                        break Some(to);
+                    }
+
+                    let next = self
+                        .moves(at, slopes)
+                        .into_iter()
+                        .find(|next| *next != previous);
+
+                    match next {
+                        Some(next) => {
+                            (previous, at) = (at, next);
+                            length += 1;
+                        }
+                        None => break None,
+                    }
+                };
+
+                if let Some(to) = reached {
+                    edges[from].push((to, length));
+                }
+            }
+        }
+
+        Ok(JunctionGraph {
+            edges,
+            start: junctions[&self.start],
+            end: junctions[&self.end],
+        })
+    }
+
+    pub fn longest_hike(&self, slopes: Slopes) -> Result<usize> {
+        self.junction_graph(slopes)?
+            .longest_path()
+            .context("no path to the end")
+    }
+}
+
+impl JunctionGraph {
+    pub fn longest_path(&self) -> Option<usize> {
+        // Most junctions have a single way into the end, so once we reach it we have to take it.
+        let into_end = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.iter().any(|(to, _)| *to == self.end))
+            .map(|(from, _)| from)
+            .collect::<Vec<_>>();
+        let last_junction = match into_end.as_slice() {
+            [only] => Some(*only),
+            _ => None,
+        };
+
+        // Upper bound for the rest of a hike: every unvisited junction is left through its longest
+        // edge at most once.
+        let longest_edge = self
+            .edges
+            .iter()
+            .map(|edges| edges.iter().map(|(_, len)| *len).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        let mut best = None;
+        self.dfs(
+            self.start,
+            1 << self.start,
+            0,
+            last_junction,
+            &longest_edge,
+            &mut best,
+        );
+        best
+    }
+
+    fn dfs(
+        &self,
+        at: usize,
+        visited: u64,
+        length: usize,
+        last_junction: Option<usize>,
+        longest_edge: &[usize],
+        best: &mut Option<usize>,
+    ) {
+        if at == self.end {
+            *best = Some(best.map_or(length, |b| b.max(length)));
+            return;
+        }
+
+        if let Some(best) = best {
+            let bound = length
+                + (0..self.edges.len())
+                    .filter(|n| visited & (1 << n) == 0 || *n == at)
+                    .map(|n| longest_edge[n])
+                    .sum::<usize>();
+            if bound <= *best {
+                return;
+            }
+        }
+
+        for &(to, len) in &self.edges[at] {
+            if visited & (1 << to) != 0 {
+                continue;
+            }
+
+            if Some(at) == last_junction && to != self.end {
+                continue;
+            }
+
+            self.dfs(
+                to,
+                visited | (1 << to),
+                length + len,
+                last_junction,
+                longest_edge,
+                best,
+            );
+        }
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    let map = parse_grid(s, |c| match c {
+        '.' => Ok(Tile::Path),
+        '#' => Ok(Tile::Forest),
+        c => Direction::try_from(c).map(Tile::Slope),
+    })?;
+
+    let opening = |row: usize| {
+        map.iter_row(row)
+            .position(|t| *t == Tile::Path)
+            .map(|col| (row as i64, col as i64))
+    };
+
+    let start = opening(0).context("expected an opening in the first row")?;
+    let end = opening(map.rows() - 1).context("expected an opening in the last row")?;
+
+    Ok(Trails { map, start, end })
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    Ok(input.longest_hike(Slopes::Slippery)? as i64)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    Ok(input.longest_hike(Slopes::Dry)? as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "#.#####################
+            #.......#########...###
+            #######.#########.#.###
+            ###.....#.>.>.###.#.###
+            ###v#####.#v#.###.#.###
+            ###.>...#.#.#.....#...#
+            ###v###.#.#.#########.#
+            ###...#.#.#.......#...#
+            #####.#.#.#######.#.###
+            #.....#.#.#.......#...#
+            #.#####.#.#.#########v#
+            #.#...#...#...###...>.#
+            #.#.#v#######v###.###v#
+            #...#.>.#...>.>.#.###.#
+            #####v#v#.###v#.#.###.#
+            #.....#...#...#.#.#...#
+            #.#########.###.#.#.###
+            #...###...#...#...#.###
+            ###.###.#.###v#####v###
+            #...#...#.#.>.>.#.>.###
+            #.###.###.#.###.#.#v###
+            #.....###...###...#...#
+            #####################.#
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 94);
+        assert_eq!(part2(&input).unwrap(), 154);
+    }
+}
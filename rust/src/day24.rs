@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use crate::{math::solve_linear_system, regex};
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive};
+
+pub type Input = Vec<Hailstone>;
+
+pub type Vec3 = [i128; 3];
+
+// Coordinates are in the hundreds of trillions, so everything here is done with integers (or exact
+// rationals) - an `f64` only has 53 bits of mantissa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    position: Vec3,
+    velocity: Vec3,
+}
+
+impl FromStr for Hailstone {
+    type Err = anyhow::Error;
+
+    // 19, 13, 30 @ -2,  1, -2
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let re = regex!(r"^(-?\d+),\s*(-?\d+),\s*(-?\d+)\s*@\s*(-?\d+),\s*(-?\d+),\s*(-?\d+)$");
+        let cap = re
+            .captures(s)
+            .with_context(|| format!("Invalid hailstone `{}`", s))?;
+
+        let n = |i: usize| cap[i].parse::<i128>().context("expected number");
+
+        Ok(Hailstone {
+            position: [n(1)?, n(2)?, n(3)?],
+            velocity: [n(4)?, n(5)?, n(6)?],
+        })
+    }
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+impl Hailstone {
+    /// Whether the XY paths of both hailstones cross in the future, inside `min..=max`.
+    pub fn paths_cross_within(&self, other: &Hailstone, min: i128, max: i128) -> bool {
+        let (p1, v1) = (self.position, self.velocity);
+        let (p2, v2) = (other.position, other.velocity);
+
+        // p1 + t * v1 = p2 + s * v2, solved with Cramer's rule.
+        let mut det = v2[0] * v1[1] - v1[0] * v2[1];
+        if det == 0 {
+            // Parallel paths never cross (the input has no hailstones sharing a path).
+            return false;
+        }
+
+        let d = sub(p2, p1);
+        let mut t = v2[0] * d[1] - d[0] * v2[1];
+        let mut s = v1[0] * d[1] - d[0] * v1[1];
+
+        // Keep the denominator positive so we can compare numerators directly.
+        if det < 0 {
+            (det, t, s) = (-det, -t, -s);
+        }
+
+        if t < 0 || s < 0 {
+            return false;
+        }
+
+        // The crossing point, scaled by `det`.
+        let x = p1[0] * det + t * v1[0];
+        let y = p1[1] * det + t * v1[1];
+
+        (min * det..=max * det).contains(&x) && (min * det..=max * det).contains(&y)
+    }
+}
+
+fn rational(n: i128) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+/// The rock `P + t * V` hits hailstone `p + t * v` iff `(P - p) x (V - v) = 0`.
+/// The `P x V` term is shared by all hailstones, so subtracting the equations of two hailstones
+/// `i` and `j` leaves a linear system:
+///   `P x (vj - vi) + (pj - pi) x V = pj x vj - pi x vi`
+/// Two such pairs give the six equations needed for `P` and `V`.
+fn rock_equations(a: &Hailstone, b: &Hailstone) -> Vec<(Vec<i128>, i128)> {
+    let w = sub(b.velocity, a.velocity);
+    let u = sub(b.position, a.position);
+    let rhs = sub(cross(b.position, b.velocity), cross(a.position, a.velocity));
+
+    vec![
+        (vec![0, w[2], -w[1], 0, -u[2], u[1]], rhs[0]),
+        (vec![-w[2], 0, w[0], u[2], 0, -u[0]], rhs[1]),
+        (vec![w[1], -w[0], 0, -u[1], u[0], 0], rhs[2]),
+    ]
+}
+
+/// Position and velocity of a rock thrown so that it hits every hailstone.
+pub fn find_rock(hailstones: &[Hailstone]) -> Result<(Vec3, Vec3)> {
+    // Any three hailstones will do, unless some of them are parallel which makes the system
+    // singular.
+    for (a, b, c) in hailstones.iter().tuple_combinations() {
+        let equations = rock_equations(a, b)
+            .into_iter()
+            .chain(rock_equations(a, c))
+            .collect::<Vec<_>>();
+
+        let (matrix, rhs): (Vec<_>, Vec<_>) = equations
+            .into_iter()
+            .map(|(row, rhs)| (row.into_iter().map(rational).collect(), rational(rhs)))
+            .unzip();
+
+        let Some(solution) = solve_linear_system(matrix, rhs) else {
+            continue;
+        };
+
+        let integers = solution
+            .iter()
+            .map(|n| {
+                (n.denom().is_one())
+                    .then(|| n.numer().to_i128())
+                    .flatten()
+                    .context("expected rock to have integer coordinates")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let position = [integers[0], integers[1], integers[2]];
+        let velocity = [integers[3], integers[4], integers[5]];
+
+        let hits_all = hailstones
+            .iter()
+            .all(|h| cross(sub(position, h.position), sub(velocity, h.velocity)) == [0, 0, 0]);
+        anyhow::ensure!(hits_all, "rock does not hit every hailstone");
+
+        return Ok((position, velocity));
+    }
+
+    anyhow::bail!("no three hailstones determine the rock")
+}
+
+pub fn parse_input(s: &str) -> Result<Input> {
+    s.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(Hailstone::from_str)
+        .collect()
+}
+
+pub fn count_crossings(input: &Input, min: i128, max: i128) -> usize {
+    input
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.paths_cross_within(b, min, max))
+        .count()
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    Ok(count_crossings(input, 200000000000000, 400000000000000) as i64)
+}
+
+pub fn part2(input: &Input) -> Result<i64> {
+    let (position, _) = find_rock(input)?;
+    i64::try_from(position.iter().sum::<i128>()).context("answer does not fit in an i64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "19, 13, 30 @ -2,  1, -2
+            18, 19, 22 @ -1, -1, -2
+            20, 25, 34 @ -2, -2, -4
+            12, 31, 28 @ -1, -2, -1
+            20, 19, 15 @  1, -5, -3
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(count_crossings(&input, 7, 27), 2);
+        assert_eq!(find_rock(&input).unwrap(), ([24, 13, 10], [-3, 1, 2]));
+        assert_eq!(part2(&input).unwrap(), 47);
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // Crossing exactly on the edge of the test area, far from the origin.
+        let input = vec![
+            Hailstone {
+                position: [300000000000001, 300000000000000, 0],
+                velocity: [-1, 0, 0],
+            },
+            Hailstone {
+                position: [300000000000000, 300000000000001, 0],
+                velocity: [0, -1, 0],
+            },
+        ];
+
+        assert_eq!(count_crossings(&input, 200000000000000, 300000000000000), 1);
+        assert_eq!(count_crossings(&input, 200000000000000, 299999999999999), 0);
+    }
+}
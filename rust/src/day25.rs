@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use crate::graph::{stoer_wagner, MinCut};
+use anyhow::{Context, Result};
+use petgraph::graph::UnGraph;
+
+pub type Input = Wiring;
+
+#[derive(Debug, Clone)]
+pub struct Wiring(UnGraph<String, ()>);
+
+impl Wiring {
+    pub fn min_cut(&self) -> Result<MinCut> {
+        stoer_wagner(&self.0, |_| 1).context("expected at least two components")
+    }
+
+    pub fn cut_edges(&self, cut: &MinCut) -> Vec<(&str, &str)> {
+        cut.edges
+            .iter()
+            .map(|e| {
+                let (a, b) = self.0.edge_endpoints(*e).expect("edge is from this graph");
+                (self.0[a].as_str(), self.0[b].as_str())
+            })
+            .collect()
+    }
+}
+
+// jqt: rhn xhk nvd
+pub fn parse_input(s: &str) -> Result<Input> {
+    let mut graph = UnGraph::new_undirected();
+    let mut nodes = HashMap::new();
+
+    let mut node = |graph: &mut UnGraph<String, ()>, name: &str| {
+        *nodes
+            .entry(name.to_string())
+            .or_insert_with(|| graph.add_node(name.to_string()))
+    };
+
+    for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let (component, others) = line
+            .split_once(':')
+            .with_context(|| format!("expected <component>: <components>, got `{}`", line))?;
+
+        let from = node(&mut graph, component.trim());
+        for other in others.split_whitespace() {
+            let to = node(&mut graph, other);
+            graph.add_edge(from, to, ());
+        }
+    }
+
+    Ok(Wiring(graph))
+}
+
+pub fn part1(input: &Input) -> Result<i64> {
+    let cut = input.min_cut()?;
+    anyhow::ensure!(
+        cut.weight == 3,
+        "expected to cut exactly 3 wires, got {}",
+        cut.weight
+    );
+
+    Ok((cut.partition.0.len() * cut.partition.1.len()) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input_str = textwrap::dedent(
+            "jqt: rhn xhk nvd
+            rsh: frs pzl lsr
+            xhk: hfx
+            cmg: qnr nvd lhk bvb
+            rhn: xhk bvb hfx
+            bvb: xhk hfx
+            pzl: lsr hfx nvd
+            qnr: nvd
+            ntq: jqt hfx bvb xhk
+            nvd: lhk
+            lsr: lhk
+            rzs: qnr cmg lsr rsh
+            frs: qnr lhk lsr
+            ",
+        );
+
+        let input = parse_input(&input_str).unwrap();
+        assert_eq!(part1(&input).unwrap(), 54);
+
+        let cut = input.min_cut().unwrap();
+        let mut edges = input
+            .cut_edges(&cut)
+            .into_iter()
+            .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+            .collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]);
+    }
+}
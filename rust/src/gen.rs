@@ -0,0 +1,470 @@
+//! Seeded synthetic puzzle inputs, for stress testing and benchmarking the solutions
+//! beyond the single real input of each day.
+//!
+//! Every generator emits text in the same format as the real input,
+//! so it goes through the day's own `parse_input`.
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// How big a generated input is. What `n` counts depends on the day,
+/// `cols` is only used by the grid days and defaults to `n` (a square grid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub n: usize,
+    pub cols: Option<usize>,
+}
+
+impl Size {
+    pub fn new(n: usize) -> Self {
+        Size { n, cols: None }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.n
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols.unwrap_or(self.n)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub day: u32,
+    /// What [`Size::n`] means for this day.
+    pub size: &'static str,
+    generate: fn(&mut ChaCha8Rng, Size) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: Size) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (self.generate)(&mut rng, size)
+    }
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        size: "lines (of `--cols` characters)",
+        generate: calibration_lines,
+    },
+    Generator {
+        day: 3,
+        size: "rows of the schematic",
+        generate: schematic,
+    },
+    Generator {
+        day: 5,
+        size: "mapping layers, ranges per layer and seed ranges",
+        generate: almanac,
+    },
+    Generator {
+        day: 6,
+        size: "races",
+        generate: races,
+    },
+    Generator {
+        day: 8,
+        size: "ghosts (at most 6)",
+        generate: ghost_maps,
+    },
+    Generator {
+        day: 14,
+        size: "rows of the platform",
+        generate: platform,
+    },
+    Generator {
+        day: 15,
+        size: "initialization steps",
+        generate: hash_steps,
+    },
+    Generator {
+        day: 16,
+        size: "rows of the mirror maze",
+        generate: mirror_maze,
+    },
+    Generator {
+        day: 17,
+        size: "rows of the heat loss grid",
+        generate: digit_grid,
+    },
+];
+
+pub fn generator(day: u32) -> Result<Generator> {
+    GENERATORS
+        .iter()
+        .find(|g| g.day == day)
+        .copied()
+        .with_context(|| {
+            let days = GENERATORS.iter().map(|g| g.day).join(", ");
+            format!("no generator for day {} (have {})", day, days)
+        })
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// `n` lines of letters mixed with digit words, which often overlap or are cut short,
+/// and at least one digit each so part 1 has an answer.
+fn calibration_lines(rng: &mut ChaCha8Rng, size: Size) -> String {
+    let mut out = String::new();
+    for _ in 0..size.rows().max(1) {
+        let cols = size.cols().max(1);
+        let mut line = String::with_capacity(cols + 5);
+        while line.len() < cols {
+            match rng.gen_range(0..10) {
+                0..=1 => line.push_str(DIGIT_WORDS.choose(rng).expect("not empty")),
+                2 => {
+                    let word = DIGIT_WORDS.choose(rng).expect("not empty");
+                    line.push_str(&word[..rng.gen_range(1..word.len())]);
+                }
+                3 => line.push(char::from_digit(rng.gen_range(1..=9), 10).unwrap()),
+                _ => line.push(rng.gen_range(b'a'..=b'z') as char),
+            }
+        }
+        line.truncate(cols);
+
+        let digit = char::from_digit(rng.gen_range(1..=9), 10).unwrap();
+        let at = rng.gen_range(0..line.len());
+        line.replace_range(at..=at, digit.encode_utf8(&mut [0; 4]));
+
+        writeln!(out, "{}", line).unwrap();
+    }
+    out
+}
+
+/// Numbers of up to three digits and symbols, at roughly the density of the real input.
+fn schematic(rng: &mut ChaCha8Rng, size: Size) -> String {
+    let cols = size.cols().max(1);
+    let mut out = String::with_capacity(size.rows() * (cols + 1));
+    for _ in 0..size.rows().max(1) {
+        let mut line = String::with_capacity(cols + 3);
+        while line.len() < cols {
+            match rng.gen_range(0..100) {
+                0..=7 => {
+                    let digits = rng.gen_range(1..=3);
+                    write!(
+                        line,
+                        "{}",
+                        rng.gen_range(10usize.pow(digits - 1)..10usize.pow(digits))
+                    )
+                    .unwrap();
+                    // Numbers on a row are always apart.
+                    line.push('.');
+                }
+                8..=10 => line.push(*b"*#+$@=-%&/".choose(rng).expect("not empty") as char),
+                _ => line.push('.'),
+            }
+        }
+        line.truncate(cols);
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+fn category(i: usize) -> String {
+    match CATEGORIES.get(i) {
+        Some(name) => name.to_string(),
+        None => format!("layer{}", i),
+    }
+}
+
+/// `n` layers with up to `n` ranges each, and `n` seed ranges.
+///
+/// Source ranges within a layer never overlap each other (like the real input), but are
+/// placed at random so the seed ranges straddle their boundaries and the gaps between them.
+fn almanac(rng: &mut ChaCha8Rng, size: Size) -> String {
+    let n = size.n.max(1);
+    let span = 1000 * n as i64;
+
+    let seeds = (0..n)
+        .flat_map(|_| {
+            let start = rng.gen_range(0..span);
+            let len = rng.gen_range(1..=span / n as i64);
+            [start, len]
+        })
+        .join(" ");
+
+    let mut out = format!("seeds: {}\n", seeds);
+    for layer in 0..n {
+        write!(
+            out,
+            "\n{}-to-{} map:\n",
+            category(layer),
+            category(layer + 1)
+        )
+        .unwrap();
+
+        // Pairs of sorted, distinct cut points are disjoint, non-empty source ranges.
+        let cuts = rand::seq::index::sample(rng, span as usize, 2 * n)
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        for (start, end) in cuts.into_iter().tuples() {
+            let (start, end) = (start as i64, end as i64);
+            let dest = rng.gen_range(0..span);
+            writeln!(out, "{} {} {}", dest, start, end - start).unwrap();
+        }
+    }
+
+    out
+}
+
+/// `n` winnable races with times below 100. Half of the records can be exactly matched by
+/// some hold time, which is where the quadratic roots are exact integers.
+fn races(rng: &mut ChaCha8Rng, size: Size) -> String {
+    let (times, distances): (Vec<i64>, Vec<i64>) = (0..size.n.max(1))
+        .map(|_| {
+            let time = rng.gen_range(2..100);
+            let best = (time / 2) * (time - time / 2);
+            let distance = if rng.gen_bool(0.5) {
+                let hold = rng.gen_range(1..time);
+                hold * (time - hold)
+            } else {
+                rng.gen_range(0..best)
+            };
+            (time, distance.min(best - 1))
+        })
+        .unzip();
+
+    format!(
+        "Time: {}\nDistance: {}\n",
+        times.iter().join(" "),
+        distances.iter().join(" ")
+    )
+}
+
+/// Node names are three characters, ending with neither 'A' nor 'Z'.
+const NODE_ALPHABET: &[u8] = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+
+fn node_name(i: usize) -> String {
+    let base = NODE_ALPHABET.len();
+    [i / (base * base), i / base, i]
+        .iter()
+        .map(|digit| NODE_ALPHABET[digit % base] as char)
+        .collect()
+}
+
+/// `n` ghosts, each walking a loop through its own nodes from `..A` to `..Z` and back again.
+///
+/// Each loop is a multiple of the (prime) number of instructions. For half the seeds it's times
+/// a distinct prime, like in the real input. For the other half it's times any small factor,
+/// so loops share factors or aren't a product of distinct primes, and the ghosts meet at the
+/// least common multiple of the loops all the same.
+/// The direction not taken at each step leads to another random node.
+fn ghost_maps(rng: &mut ChaCha8Rng, size: Size) -> String {
+    const PRIMES: [usize; 7] = [3, 5, 7, 11, 13, 17, 19];
+    let n = size.n.clamp(1, PRIMES.len() - 1);
+
+    let mut primes = PRIMES.to_vec();
+    primes.shuffle(rng);
+    let n_instructions = primes.pop().expect("not empty");
+    let instructions = (0..n_instructions)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect::<String>();
+
+    let factors = if rng.gen_bool(0.5) {
+        primes.into_iter().take(n).collect_vec()
+    } else {
+        (0..n).map(|_| rng.gen_range(2..=9)).collect_vec()
+    };
+
+    let mut loops = vec![];
+    let mut next_node = 0;
+    for (ghost, factor) in factors.into_iter().enumerate() {
+        let c = (b'A' + ghost as u8) as char;
+        let len = n_instructions * factor;
+
+        let mut nodes = vec![format!("{}{}A", c, c)];
+        nodes.extend((1..len).map(|i| node_name(next_node + i)));
+        nodes.push(format!("{}{}Z", c, c));
+        next_node += len;
+        loops.push(nodes);
+    }
+
+    let all_nodes = loops.iter().flatten().cloned().collect_vec();
+    let mut lines = vec![];
+    for nodes in &loops {
+        let len = nodes.len() - 1;
+        for (i, node) in nodes.iter().enumerate() {
+            // From `..Z` the loop starts over, with the same instructions as from `..A`.
+            let step = i % len;
+            let next = &nodes[step + 1];
+            let other = all_nodes.choose(rng).expect("not empty");
+
+            let (left, right) = match instructions.as_bytes()[step % n_instructions] {
+                b'L' => (next, other),
+                _ => (other, next),
+            };
+            lines.push(format!("{} = ({}, {})", node, left, right));
+        }
+    }
+    lines.shuffle(rng);
+
+    format!("{}\n\n{}\n", instructions, lines.join("\n"))
+}
+
+fn platform(rng: &mut ChaCha8Rng, size: Size) -> String {
+    grid(rng, size, |rng| match rng.gen_range(0..10) {
+        0..=4 => '.',
+        5..=7 => 'O',
+        _ => '#',
+    })
+}
+
+/// `n` comma separated steps, over a pool of labels small enough that
+/// labels get replaced and removed.
+fn hash_steps(rng: &mut ChaCha8Rng, size: Size) -> String {
+    let n = size.n.max(1);
+    let labels = (0..(n / 4).clamp(1, 2000))
+        .map(|_| {
+            let len = rng.gen_range(2..=6);
+            (0..len)
+                .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                .collect::<String>()
+        })
+        .collect_vec();
+
+    (0..n)
+        .map(|_| {
+            let label = labels.choose(rng).expect("at least one label");
+            if rng.gen_bool(0.6) {
+                format!("{}={}", label, rng.gen_range(1..=9))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .join(",")
+}
+
+fn grid(rng: &mut ChaCha8Rng, size: Size, mut tile: impl FnMut(&mut ChaCha8Rng) -> char) -> String {
+    let mut out = String::with_capacity(size.rows() * (size.cols() + 1));
+    for _ in 0..size.rows().max(1) {
+        out.extend((0..size.cols().max(1)).map(|_| tile(rng)));
+        out.push('\n');
+    }
+    out
+}
+
+/// Mostly empty space, with mirrors and splitters sprinkled in at roughly the density
+/// of the real input.
+fn mirror_maze(rng: &mut ChaCha8Rng, size: Size) -> String {
+    grid(rng, size, |rng| {
+        if rng.gen_bool(0.9) {
+            '.'
+        } else {
+            *['/', '\\', '|', '-'].choose(rng).expect("not empty")
+        }
+    })
+}
+
+fn digit_grid(rng: &mut ChaCha8Rng, size: Size) -> String {
+    grid(rng, size, |rng| {
+        char::from_digit(rng.gen_range(1..=9), 10).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        for generator in GENERATORS {
+            let a = generator.generate(7, Size::new(10));
+            let b = generator.generate(7, Size::new(10));
+            let c = generator.generate(8, Size::new(10));
+            assert_eq!(a, b, "day {}", generator.day);
+            assert_ne!(a, c, "day {}", generator.day);
+        }
+    }
+
+    #[test]
+    fn test_parses_and_solves() {
+        for seed in 0..10 {
+            let size = Size {
+                n: 20,
+                cols: Some(50),
+            };
+            let input = generator(1).unwrap().generate(seed, size);
+            assert!(input.lines().all(|l| l.len() == 50));
+            let lines = crate::day01::parse_input(&input).unwrap();
+            crate::day01::part1(&lines).unwrap();
+            crate::day01::part2(&lines).unwrap();
+
+            let input = generator(3).unwrap().generate(seed, Size::new(20));
+            assert!(input.lines().all(|l| l.len() == 20));
+            let schematic = crate::day03::parse_input(&input).unwrap();
+            crate::day03::part1(&schematic).unwrap();
+            crate::day03::part2(&schematic).unwrap();
+
+            let input = generator(5).unwrap().generate(seed, Size::new(8));
+            let almanac = crate::day05::parse_input(&input).unwrap();
+            crate::day05::part1(&almanac).unwrap();
+            crate::day05::part2(&almanac).unwrap();
+
+            let input = generator(6).unwrap().generate(seed, Size::new(3));
+            let races = crate::day06::parse_input(&input).unwrap();
+            assert_eq!(races.len(), 3);
+            crate::day06::part1(&races).unwrap();
+
+            let input = generator(8).unwrap().generate(seed, Size::new(3));
+            let maps = crate::day08::parse_input(&input).unwrap();
+            crate::day08::part1(&maps).unwrap();
+            crate::day08::part2(&maps).unwrap();
+
+            let input = generator(14).unwrap().generate(seed, Size::new(10));
+            let platform = crate::day14::parse_input(&input).unwrap();
+            crate::day14::part2(&platform).unwrap();
+
+            let input = generator(15).unwrap().generate(seed, Size::new(200));
+            let steps = crate::day15::parse_input(&input).unwrap();
+            assert_eq!(steps.len(), 200);
+            crate::day15::part2(&steps).unwrap();
+
+            let size = Size {
+                n: 12,
+                cols: Some(30),
+            };
+            let input = generator(16).unwrap().generate(seed, size);
+            assert_eq!(input.lines().count(), 12);
+            assert!(input.lines().all(|l| l.len() == 30));
+            let maze = crate::day16::parse_input(&input).unwrap();
+            crate::day16::part2(&maze).unwrap();
+
+            let input = generator(17).unwrap().generate(seed, Size::new(15));
+            let maze = crate::day17::parse_input(&input).unwrap();
+            crate::day17::part1(&maze).unwrap();
+            crate::day17::part2(&maze).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_almanac_layers() {
+        let input = generator(5).unwrap().generate(1, Size::new(9));
+        let headers = input.lines().filter(|l| l.ends_with("map:")).collect_vec();
+        assert_eq!(headers.len(), 9);
+        assert_eq!(headers[0], "seed-to-soil map:");
+        assert_eq!(headers[6], "humidity-to-location map:");
+        assert_eq!(headers[8], "layer8-to-layer9 map:");
+    }
+}
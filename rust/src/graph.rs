@@ -0,0 +1,165 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use petgraph::{
+    graph::{EdgeIndex, NodeIndex, UnGraph},
+    visit::EdgeRef,
+};
+
+/// A global minimum cut of an undirected graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    pub weight: i64,
+    // The two sides of the cut, each non empty.
+    pub partition: (Vec<NodeIndex>, Vec<NodeIndex>),
+    // Edges going from one side to the other.
+    pub edges: Vec<EdgeIndex>,
+}
+
+/// Stoer-Wagner global min cut, `O(V * E * log(V))`.
+/// Each phase orders the (merged) nodes by maximum adjacency, the last node of the ordering is
+/// separated from the rest by a minimum cut between it and the node before it. Merging the two
+/// and repeating `V - 1` times visits every candidate.
+/// `None` if the graph has less than two nodes.
+pub fn stoer_wagner<N, E>(graph: &UnGraph<N, E>, weight: impl Fn(&E) -> i64) -> Option<MinCut> {
+    let n = graph.node_count();
+    if n < 2 {
+        return None;
+    }
+
+    // adjacency[v][u] = total weight between merged nodes v and u
+    let mut adjacency = vec![HashMap::<usize, i64>::new(); n];
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            *adjacency[a].entry(b).or_default() += weight(edge.weight());
+            *adjacency[b].entry(a).or_default() += weight(edge.weight());
+        }
+    }
+
+    let mut merged = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+    let mut active = (0..n).collect::<Vec<_>>();
+    let mut best: Option<(i64, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        let mut connectivity = vec![0; n];
+        let mut added = vec![false; n];
+        let mut heap = active.iter().map(|&v| (0, v)).collect::<BinaryHeap<_>>();
+        let (mut s, mut t) = (active[0], active[0]);
+        let mut cut_of_phase = 0;
+
+        while let Some((w, v)) = heap.pop() {
+            // Stale entry, a newer one with a higher weight was already popped.
+            if added[v] || w != connectivity[v] {
+                continue;
+            }
+
+            added[v] = true;
+            (s, t) = (t, v);
+            cut_of_phase = w;
+
+            for (&u, &w) in &adjacency[v] {
+                if !added[u] {
+                    connectivity[u] += w;
+                    heap.push((connectivity[u], u));
+                }
+            }
+        }
+
+        if best
+            .as_ref()
+            .map_or(true, |(weight, _)| cut_of_phase < *weight)
+        {
+            best = Some((cut_of_phase, merged[t].clone()));
+        }
+
+        // Merge t into s.
+        let t_edges = std::mem::take(&mut adjacency[t]);
+        for (u, w) in t_edges {
+            adjacency[u].remove(&t);
+            if u != s {
+                *adjacency[s].entry(u).or_default() += w;
+                *adjacency[u].entry(s).or_default() += w;
+            }
+        }
+        let t_nodes = std::mem::take(&mut merged[t]);
+        merged[s].extend(t_nodes);
+        active.retain(|&v| v != t);
+    }
+
+    let (weight, side) = best?;
+    let side = side.into_iter().collect::<HashSet<_>>();
+
+    let (a, b) = graph
+        .node_indices()
+        .partition(|node| side.contains(&node.index()));
+
+    let edges = graph
+        .edge_references()
+        .filter(|e| side.contains(&e.source().index()) != side.contains(&e.target().index()))
+        .map(|e| e.id())
+        .collect();
+
+    Some(MinCut {
+        weight,
+        partition: (a, b),
+        edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stoer_wagner() {
+        // Two triangles joined by a single edge.
+        let graph =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]);
+
+        let cut = stoer_wagner(&graph, |_| 1).unwrap();
+        assert_eq!(cut.weight, 1);
+        assert_eq!(cut.partition.0.len(), 3);
+        assert_eq!(cut.partition.1.len(), 3);
+        assert_eq!(cut.edges.len(), 1);
+        assert_eq!(
+            graph.edge_endpoints(cut.edges[0]),
+            Some((NodeIndex::new(2), NodeIndex::new(3)))
+        );
+    }
+
+    #[test]
+    fn test_stoer_wagner_weighted() {
+        // The example from the Stoer-Wagner paper, the minimum cut is {2, 3, 6, 7} with weight 4.
+        let graph = UnGraph::<(), i64>::from_edges([
+            (0, 1, 2),
+            (0, 4, 3),
+            (1, 2, 3),
+            (1, 4, 2),
+            (1, 5, 2),
+            (2, 3, 4),
+            (2, 6, 2),
+            (3, 6, 2),
+            (3, 7, 2),
+            (4, 5, 3),
+            (5, 6, 1),
+            (6, 7, 3),
+        ]);
+
+        let cut = stoer_wagner(&graph, |w| *w).unwrap();
+        assert_eq!(cut.weight, 4);
+
+        let mut smaller = if cut.partition.0.len() < cut.partition.1.len() {
+            cut.partition.0
+        } else {
+            cut.partition.1
+        };
+        smaller.sort();
+        assert_eq!(smaller, [2, 3, 6, 7].map(NodeIndex::new).to_vec());
+    }
+
+    #[test]
+    fn test_too_small() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 0)]);
+        assert_eq!(stoer_wagner(&graph, |_| 1), None);
+    }
+}
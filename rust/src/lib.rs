@@ -1,4 +1,38 @@
+use anyhow::{Context, Result};
 use grid::Grid;
+use serde::Serialize;
+
+pub mod alloc;
+pub mod client;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod gen;
+pub mod graph;
+pub mod math;
+pub mod oracle;
+pub mod runner;
+pub mod trace;
 
 #[macro_export]
 macro_rules! regex {
@@ -24,7 +58,7 @@ pub mod testing {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize)]
 pub enum Direction {
     Left,
     Right,
@@ -41,6 +75,28 @@ impl Direction {
             Direction::Down => Direction::Up,
         }
     }
+
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+}
+
+// Both arrows (`^>v<`) and letters (`UDLR`) show up in puzzle inputs.
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            '^' | 'U' => Direction::Up,
+            'v' | 'D' => Direction::Down,
+            '<' | 'L' => Direction::Left,
+            '>' | 'R' => Direction::Right,
+            _ => anyhow::bail!("Invalid direction `{}`", c),
+        })
+    }
 }
 
 pub trait CoordExt {
@@ -48,6 +104,7 @@ pub trait CoordExt {
     fn right(&self) -> (i64, i64);
     fn up(&self) -> (i64, i64);
     fn down(&self) -> (i64, i64);
+    fn step(&self, direction: Direction) -> (i64, i64);
 }
 
 impl CoordExt for (i64, i64) {
@@ -66,11 +123,22 @@ impl CoordExt for (i64, i64) {
     fn down(&self) -> (i64, i64) {
         (self.0 + 1, self.1)
     }
+
+    fn step(&self, direction: Direction) -> (i64, i64) {
+        match direction {
+            Direction::Left => self.left(),
+            Direction::Right => self.right(),
+            Direction::Up => self.up(),
+            Direction::Down => self.down(),
+        }
+    }
 }
 
 pub trait GridExt<T> {
     // Grid expects usize, and we always need to manually check for bounds which is annoying.
     fn get_coordinate(&self, coord: (i64, i64)) -> Option<&T>;
+    // Treats the grid as infinitely tiled in every direction.
+    fn get_coordinate_wrapping(&self, coord: (i64, i64)) -> Option<&T>;
 }
 
 impl<T> GridExt<T> for Grid<T> {
@@ -82,4 +150,45 @@ impl<T> GridExt<T> for Grid<T> {
             self.get(coord.0 as usize, coord.1 as usize)
         }
     }
+
+    fn get_coordinate_wrapping(&self, coord: (i64, i64)) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let row = coord.0.rem_euclid(self.rows() as i64);
+        let col = coord.1.rem_euclid(self.cols() as i64);
+        self.get(row as usize, col as usize)
+    }
+}
+
+/// Parses lines of characters into a grid, trimming each line.
+pub fn parse_grid<T>(s: &str, mut parse: impl FnMut(char) -> Result<T>) -> Result<Grid<T>> {
+    let lines = s
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>();
+
+    let cols = lines
+        .first()
+        .context("expected grid to be not empty")?
+        .chars()
+        .count();
+    let mut cells = Vec::with_capacity(lines.len() * cols);
+
+    for (i, line) in lines.iter().enumerate() {
+        anyhow::ensure!(
+            line.chars().count() == cols,
+            "expected line {} to have {} columns",
+            i,
+            cols
+        );
+
+        for c in line.chars() {
+            cells.push(parse(c)?);
+        }
+    }
+
+    Ok(Grid::from_vec(cells, cols))
 }
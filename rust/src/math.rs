@@ -0,0 +1,206 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num_traits::Num;
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// LCM of all the numbers, `None` for an empty iterator.
+pub fn lcm_all(numbers: impl IntoIterator<Item = i64>) -> Option<i64> {
+    numbers.into_iter().reduce(lcm)
+}
+
+/// A sequence of states that repeats itself after `start`, every `length` states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the first state equal to the `n`-th state of the sequence.
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the first state that repeats in `states`.
+/// Only stores the states seen so far, so the iterator may be infinite as long as it cycles.
+pub fn find_cycle<T: Hash + Eq>(states: impl IntoIterator<Item = T>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+
+    for (i, state) in states.into_iter().enumerate() {
+        if let Some(start) = seen.insert(state, i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+    }
+
+    None
+}
+
+/// Repeatedly takes the differences between consecutive elements, until they're all zeros.
+/// The first row is the sequence itself.
+pub fn calc_deltas_recursive(sequence: &[i64]) -> Vec<Vec<i64>> {
+    let mut deltas = vec![sequence.to_vec()];
+
+    let mut current = sequence;
+
+    while {
+        let delta: Vec<i64> = current.windows(2).map(|w| w[1] - w[0]).collect();
+        let stop = delta.iter().all(|&d| d == 0); // stop when all delta is zeros.
+        deltas.push(delta); // calculate stop first to avoid clone :)
+        !stop
+    } {
+        current = deltas.last().unwrap();
+    }
+
+    deltas
+}
+
+/// The `n`-th element (0 based) of the polynomial sequence starting with `sequence`, using
+/// Newton's forward differences.
+/// `None` if `sequence` is too short for the differences to settle into zeros.
+pub fn extrapolate(sequence: &[i64], n: i64) -> Option<i64> {
+    let deltas = calc_deltas_recursive(sequence);
+
+    // If we ran out of elements before reaching a row of zeros, the last row is empty.
+    if deltas.last()?.is_empty() {
+        return None;
+    }
+
+    let mut value = 0;
+    let mut binomial = 1; // n choose k
+
+    for (k, row) in deltas.iter().enumerate() {
+        let k = k as i64;
+        value += row.first()? * binomial;
+        binomial = binomial * (n - k) / (k + 1);
+    }
+
+    Some(value)
+}
+
+/// Solves `a * x = b` with Gauss-Jordan elimination.
+/// The elimination divides, so `T` should be exact (e.g. `BigRational`) - floats lose precision
+/// and integers truncate.
+/// `None` if the system doesn't have a unique solution.
+pub fn solve_linear_system<T: Num + Clone>(mut a: Vec<Vec<T>>, mut b: Vec<T>) -> Option<Vec<T>> {
+    let n = a.len();
+    if b.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    for col in 0..n {
+        let pivot = (col..n).find(|&row| !a[row][col].is_zero())?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col].clone();
+        for row in 0..n {
+            if row == col || a[row][col].is_zero() {
+                continue;
+            }
+
+            let factor = a[row][col].clone() / pivot_row[col].clone();
+            for (cell, pivot) in a[row].iter_mut().zip(&pivot_row).skip(col) {
+                *cell = cell.clone() - factor.clone() * pivot.clone();
+            }
+            let delta = factor * b[col].clone();
+            b[row] = b[row].clone() - delta;
+        }
+    }
+
+    Some(
+        b.into_iter()
+            .zip(a)
+            .enumerate()
+            .map(|(i, (b, row))| b / row[i].clone())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm_all([3, 4, 5, 6]), Some(60));
+        assert_eq!(lcm_all([]), None);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle([1, 2, 3, 4, 2, 3, 4, 2]).unwrap();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 1,
+                length: 3
+            }
+        );
+        assert_eq!(cycle.index_of(7), 1);
+        assert_eq!(cycle.index_of(9), 3);
+        assert_eq!(cycle.index_of(0), 0);
+
+        assert_eq!(find_cycle([1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // n^2 + 1
+        assert_eq!(extrapolate(&[1, 2, 5, 10], 4), Some(17));
+        assert_eq!(extrapolate(&[1, 2, 5, 10], 100), Some(10001));
+        assert_eq!(extrapolate(&[1, 2, 5, 10], 0), Some(1));
+        assert_eq!(extrapolate(&[3, 3], 10), Some(3));
+
+        // Not enough samples to tell it's a quadratic.
+        assert_eq!(extrapolate(&[1, 2, 5], 4), None);
+    }
+
+    #[test]
+    fn test_solve_linear_system() {
+        use num_rational::Ratio;
+
+        let r = |n: i64| Ratio::from_integer(n);
+
+        // x + y = 3, x - y = 1
+        let a = vec![vec![r(1), r(1)], vec![r(1), r(-1)]];
+        assert_eq!(
+            solve_linear_system(a, vec![r(3), r(1)]),
+            Some(vec![r(2), r(1)])
+        );
+
+        // Needs a row swap, and has a fractional solution.
+        let a = vec![vec![r(0), r(2)], vec![r(3), r(0)]];
+        assert_eq!(
+            solve_linear_system(a, vec![r(1), r(1)]),
+            Some(vec![Ratio::new(1, 3), Ratio::new(1, 2)])
+        );
+
+        // Singular
+        let a = vec![vec![r(1), r(2)], vec![r(2), r(4)]];
+        assert_eq!(solve_linear_system(a, vec![r(1), r(2)]), None);
+    }
+}
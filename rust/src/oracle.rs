@@ -0,0 +1,311 @@
+//! Differential testing of the solutions that rely on a trick against brute-force references.
+//!
+//! Inputs come from [`crate::gen`], starting with the smallest sizes, and any mismatch is
+//! shrunk by dropping lines and numbers for as long as it keeps mismatching the same way.
+//! Both sides failing is not a mismatch, even if they fail differently.
+
+use std::fmt::Display;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::gen::{self, Size};
+use crate::trace::NoTrace;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub seed: u64,
+    pub size: Size,
+    /// The shrunk input.
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "day {} (seed {}, size {}): expected {}, got {}",
+            self.day, self.seed, self.size.n, self.expected, self.actual
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Differential {
+    pub day: u32,
+    /// Runs the reference and the solution on an input, `None` if it doesn't parse.
+    compare: fn(&str) -> Option<(Answer, Answer)>,
+}
+
+/// An answer, or why there is none.
+type Answer = std::result::Result<String, String>;
+
+fn answer<T: Display>(result: Result<T>) -> Answer {
+    result.map(|a| a.to_string()).map_err(|e| e.to_string())
+}
+
+/// The answers to several parts, separated by commas, or the first error.
+fn answers<T: Display>(results: impl IntoIterator<Item = Result<T>>) -> Answer {
+    results
+        .into_iter()
+        .map(answer)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map(|answers| answers.join(", "))
+}
+
+fn show(answer: Answer) -> String {
+    answer.unwrap_or_else(|e| format!("error: {}", e))
+}
+
+pub static DIFFERENTIALS: &[Differential] = &[
+    Differential {
+        day: 1,
+        compare: |s| {
+            let input = crate::day01::parse_input(s).ok()?;
+            Some((
+                answer(crate::day01::part2_regex(
+                    &input,
+                    &crate::day01::DigitLexicon::english(),
+                )),
+                answer(crate::day01::part2(&input)),
+            ))
+        },
+    },
+    Differential {
+        day: 5,
+        compare: |s| {
+            let input = crate::day05::parse_input(s).ok()?;
+            Some((
+                answer(crate::day05::part2_naive(&input)),
+                answer(crate::day05::part2(&input)),
+            ))
+        },
+    },
+    Differential {
+        day: 6,
+        compare: |s| {
+            let input = crate::day06::parse_input(s).ok()?;
+            let expected = [
+                crate::day06::part1_naive(&input),
+                crate::day06::part2_naive(&input),
+            ];
+            let actual = [crate::day06::part1(&input), crate::day06::part2(&input)];
+            Some((answers(expected), answers(actual)))
+        },
+    },
+    Differential {
+        day: 8,
+        compare: |s| {
+            let input = crate::day08::parse_input(s).ok()?;
+            Some((
+                answer(crate::day08::part2_naive(&input, 10_000_000)),
+                answer(crate::day08::part2(&input)),
+            ))
+        },
+    },
+    Differential {
+        day: 14,
+        compare: |s| {
+            let input = crate::day14::parse_input(s).ok()?;
+            let cycles = [0, 1, 2, 5, 17, 100, 1000];
+            Some((
+                Ok(cycles
+                    .iter()
+                    .map(|&n| crate::day14::load_after_cycles_naive(&input, n))
+                    .join(", ")),
+                answers(
+                    cycles
+                        .iter()
+                        .map(|&n| crate::day14::load_after_cycles(&input, n, &mut NoTrace)),
+                ),
+            ))
+        },
+    },
+];
+
+pub fn differential(day: u32) -> Option<Differential> {
+    DIFFERENTIALS.iter().find(|d| d.day == day).copied()
+}
+
+impl Differential {
+    fn mismatch(&self, input: &str) -> Option<(Answer, Answer)> {
+        (self.compare)(input).filter(|answers| match answers {
+            (Ok(expected), Ok(actual)) => expected != actual,
+            (Err(_), Err(_)) => false,
+            _ => true,
+        })
+    }
+
+    /// Like [`Self::mismatch`], but only if the same sides fail as in `like`, so an input with two
+    /// different answers doesn't shrink into one the reference gives up on. A candidate that
+    /// panics (e.g. a parser indexing into a line that lost a number) is not a smaller mismatch.
+    fn still_mismatches(&self, input: &str, like: &(Answer, Answer)) -> bool {
+        std::panic::catch_unwind(|| {
+            self.mismatch(input).is_some_and(|(expected, actual)| {
+                expected.is_ok() == like.0.is_ok() && actual.is_ok() == like.1.is_ok()
+            })
+        })
+        .unwrap_or(false)
+    }
+
+    /// Compares on generated inputs of every size up to `max_size`, for every seed.
+    /// Returns the first (smallest) mismatch, shrunk.
+    pub fn check(&self, seeds: std::ops::Range<u64>, max_size: usize) -> Result<Option<Mismatch>> {
+        let generator = gen::generator(self.day)?;
+
+        for n in 1..=max_size {
+            let size = Size::new(n);
+            for seed in seeds.clone() {
+                let input = generator.generate(seed, size);
+                if self.mismatch(&input).is_some() {
+                    let input = self.shrink(input);
+                    let (expected, actual) = self.mismatch(&input).expect("shrinking keeps it");
+                    return Ok(Some(Mismatch {
+                        day: self.day,
+                        seed,
+                        size,
+                        input,
+                        expected: show(expected),
+                        actual: show(actual),
+                    }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Greedily removes whole lines, then single numbers, while the input still mismatches.
+    pub fn shrink(&self, mut input: String) -> String {
+        let Some(like) = self.mismatch(&input) else {
+            return input;
+        };
+        loop {
+            let smaller = candidates(&input).find(|c| self.still_mismatches(c, &like));
+            match smaller {
+                Some(smaller) => input = smaller,
+                None => return input,
+            }
+        }
+    }
+}
+
+/// Every input with one line removed, then every input with one token removed from a line.
+fn candidates(input: &str) -> impl Iterator<Item = String> + '_ {
+    let lines = input.lines().collect_vec();
+    let n = lines.len();
+
+    let without_line = {
+        let lines = lines.clone();
+        (0..n).map(move |skip| {
+            let mut s = lines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, l)| *l)
+                .join("\n");
+            s.push('\n');
+            s
+        })
+    };
+
+    let without_token = (0..n).flat_map(move |i| {
+        let lines = lines.clone();
+        let tokens = lines[i].split_whitespace().count();
+        (0..tokens).map(move |skip| {
+            let mut lines = lines.iter().map(|l| l.to_string()).collect_vec();
+            lines[i] = lines[i]
+                .split_whitespace()
+                .enumerate()
+                .filter(|(j, _)| *j != skip)
+                .map(|(_, t)| t)
+                .join(" ");
+            let mut s = lines.join("\n");
+            s.push('\n');
+            s
+        })
+    });
+
+    without_line.chain(without_token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(day: u32, seeds: u64, max_size: usize) {
+        let differential = differential(day).unwrap();
+        if let Some(mismatch) = differential.check(0..seeds, max_size).unwrap() {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn test_day1() {
+        check(1, 50, 10);
+    }
+
+    #[test]
+    fn test_day5() {
+        check(5, 50, 5);
+    }
+
+    #[test]
+    fn test_day6() {
+        check(6, 100, 2);
+    }
+
+    #[test]
+    fn test_day8() {
+        // Half the seeds have loops with shared or composite factors.
+        check(8, 20, 3);
+    }
+
+    #[test]
+    fn test_day14() {
+        check(14, 30, 8);
+    }
+
+    #[test]
+    fn test_shrink() {
+        // A made up "solution" that is wrong whenever a line holds a 7.
+        let differential = Differential {
+            day: 0,
+            compare: |s| {
+                Some((
+                    Ok(String::new()),
+                    Ok(if s.contains('7') { "7" } else { "" }.into()),
+                ))
+            },
+        };
+
+        let shrunk = differential.shrink("1 2\n3 7 4\n5\n".to_string());
+        assert_eq!(shrunk, "7\n");
+    }
+
+    #[test]
+    fn test_shrink_keeps_answers() {
+        // Without a 1 both sides fail, with different messages. That's not the wrong answer
+        // being shrunk, so the 1 has to stay.
+        let differential = Differential {
+            day: 0,
+            compare: |s| {
+                if !s.contains('1') {
+                    return Some((Err("no 1".into()), Err("missing 1".into())));
+                }
+                Some((
+                    Ok(String::new()),
+                    Ok(if s.contains('7') { "7" } else { "" }.into()),
+                ))
+            },
+        };
+
+        let shrunk = differential.shrink("1 2\n3 7 4\n5\n".to_string());
+        assert_eq!(shrunk, "1\n7\n");
+        assert!(differential.mismatch("2\n").is_none());
+    }
+}
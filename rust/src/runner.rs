@@ -0,0 +1,784 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::alloc::{self, AllocStats};
+use crate::day01::{self, LineCalibration, NoDigit};
+use crate::day02::{self, Bag, Game};
+use crate::trace::{Event, NoTrace, Tracer};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Error(String),
+    Panic(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "error: {}", e),
+            Failure::Panic(p) => write!(f, "panic: {}", p),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseReport {
+    pub phase: Phase,
+    pub elapsed: Duration,
+    /// The answer for a part, empty for [`Phase::Parse`].
+    pub result: std::result::Result<String, Failure>,
+    /// Only measured with the `count-alloc` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u32,
+    pub phases: Vec<PhaseReport>,
+    pub elapsed: Duration,
+}
+
+impl DayReport {
+    fn new(day: u32) -> Self {
+        DayReport {
+            day,
+            phases: vec![],
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn get(&self, phase: Phase) -> Option<&PhaseReport> {
+        self.phases.iter().find(|p| p.phase == phase)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PhaseReport> {
+        self.phases.iter().filter(|p| p.result.is_err())
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Runs a single phase, catching both errors and panics.
+    /// Returns `None` if the phase failed, so later phases can be skipped.
+    fn phase<T>(&mut self, phase: Phase, f: impl FnOnce() -> Result<T>) -> Option<T> {
+        let start = Instant::now();
+        let (outcome, alloc) = if alloc::ENABLED {
+            let (outcome, stats) = alloc::measure(|| catch(f));
+            (outcome, Some(stats))
+        } else {
+            (catch(f), None)
+        };
+        let elapsed = start.elapsed();
+
+        let (result, value) = match outcome {
+            Ok(value) => (Ok(String::new()), Some(value)),
+            Err(failure) => (Err(failure), None),
+        };
+
+        self.phases.push(PhaseReport {
+            phase,
+            elapsed,
+            result,
+            alloc,
+        });
+        value
+    }
+
+    fn part<T: Display>(&mut self, phase: Phase, f: impl FnOnce() -> Result<T>) {
+        if let Some(answer) = self.phase(phase, f) {
+            let report = self.phases.last_mut().expect("phase was just pushed");
+            report.result = Ok(answer.to_string());
+        }
+    }
+}
+
+thread_local! {
+    static LAST_PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replaces the default panic hook (which prints to stderr as soon as a day panics)
+/// with one that only keeps the location for the report.
+pub fn capture_panics() {
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| l.to_string());
+        LAST_PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+    }));
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    };
+
+    match LAST_PANIC_LOCATION.with(|last| last.borrow_mut().take()) {
+        Some(location) => format!("{} ({})", message, location),
+        None => message,
+    }
+}
+
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> std::result::Result<T, Failure> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Failure::Error(format!("{:#}", e))),
+        Err(payload) => Err(Failure::Panic(panic_message(payload))),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// One `Debug` formatted event per line.
+    Text,
+    /// One JSON object per line, with the day and phase alongside the event's fields.
+    Json,
+}
+
+/// Collects the events of all the days being run, tagged with their day and phase.
+/// Days run in parallel, so lines of different days interleave.
+pub struct TraceSink {
+    format: TraceFormat,
+    out: Mutex<Box<dyn Write + Send>>,
+    error: Mutex<Option<io::Error>>,
+}
+
+impl TraceSink {
+    pub fn new(format: TraceFormat, out: impl Write + Send + 'static) -> Self {
+        TraceSink {
+            format,
+            out: Mutex::new(Box::new(out)),
+            error: Mutex::new(None),
+        }
+    }
+
+    fn write(&self, day: u32, phase: Phase, event: Event) {
+        let line = match self.format {
+            TraceFormat::Text => format!("day {} {:?}: {:?}", day, phase, event),
+            TraceFormat::Json => {
+                let mut value = serde_json::to_value(&event).expect("events serialize");
+                value["day"] = day.into();
+                value["phase"] = serde_json::to_value(phase).expect("phases serialize");
+                value.to_string()
+            }
+        };
+
+        let mut out = self.out.lock().expect("not poisoned");
+        if let Err(e) = writeln!(out, "{}", line) {
+            self.error.lock().expect("not poisoned").get_or_insert(e);
+        }
+    }
+
+    /// Flushes the output, returning the first error hit while writing events.
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(e) = self.error.into_inner().expect("not poisoned") {
+            return Err(e);
+        }
+        self.out.into_inner().expect("not poisoned").flush()
+    }
+
+    fn tracer(&self, day: u32, phase: Phase) -> SinkTracer<'_> {
+        SinkTracer {
+            sink: self,
+            day,
+            phase,
+        }
+    }
+}
+
+struct SinkTracer<'a> {
+    sink: &'a TraceSink,
+    day: u32,
+    phase: Phase,
+}
+
+impl Tracer for SinkTracer<'_> {
+    fn event(&mut self, event: Event) {
+        self.sink.write(self.day, self.phase, event);
+    }
+}
+
+/// Hands out the tracer for each phase of a day.
+type Tracers<'a> = &'a dyn Fn(Phase) -> Box<dyn Tracer + 'a>;
+
+/// Variations on the puzzles, for the days that take any.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Day 2: the bag the games are played with, instead of the puzzle's.
+    pub bag: Option<Bag>,
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    solve: fn(&mut DayReport, &str, Tracers<'_>, &Options),
+}
+
+impl Day {
+    /// Solves the day, sending the events of the days that support tracing to `trace`.
+    pub fn run(&self, input: &str, trace: Option<&TraceSink>) -> DayReport {
+        self.run_with(input, trace, &Options::default())
+    }
+
+    pub fn run_with(&self, input: &str, trace: Option<&TraceSink>, options: &Options) -> DayReport {
+        let tracers = |phase| -> Box<dyn Tracer + '_> {
+            match trace {
+                Some(sink) => Box::new(sink.tracer(self.number, phase)),
+                None => Box::new(NoTrace),
+            }
+        };
+
+        let mut report = DayReport::new(self.number);
+        let start = Instant::now();
+        (self.solve)(&mut report, input, &tracers, options);
+        report.elapsed = start.elapsed();
+        report
+    }
+
+    pub fn input_path(&self, input_dir: &Path) -> PathBuf {
+        input_dir.join(format!("day{}.txt", self.number))
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day").field("number", &self.number).finish()
+    }
+}
+
+macro_rules! day {
+    ($number:literal, $module:ident) => {
+        Day {
+            number: $number,
+            solve: |report, s, _, _| {
+                if let Some(input) = report.phase(Phase::Parse, || crate::$module::parse_input(s)) {
+                    report.part(Phase::Part1, || crate::$module::part1(&input));
+                    report.part(Phase::Part2, || crate::$module::part2(&input));
+                }
+            },
+        }
+    };
+    // Days with `part1_traced` and `part2_traced`.
+    ($number:literal, $module:ident, traced) => {
+        Day {
+            number: $number,
+            solve: |report, s, tracers, _| {
+                if let Some(input) = report.phase(Phase::Parse, || crate::$module::parse_input(s)) {
+                    report.part(Phase::Part1, || {
+                        crate::$module::part1_traced(&input, &mut *tracers(Phase::Part1))
+                    });
+                    report.part(Phase::Part2, || {
+                        crate::$module::part2_traced(&input, &mut *tracers(Phase::Part2))
+                    });
+                }
+            },
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, day01),
+    Day {
+        number: 2,
+        solve: |report, s, _, options| {
+            let bag = options.bag.clone().unwrap_or_else(Bag::puzzle);
+            if let Some(input) = report.phase(Phase::Parse, || crate::day02::parse_input(s)) {
+                report.part(Phase::Part1, || crate::day02::part1_with(&input, &bag));
+                report.part(Phase::Part2, || crate::day02::part2_with(&input, &bag));
+            }
+        },
+    },
+    day!(3, day03),
+    day!(4, day04),
+    day!(5, day05, traced),
+    day!(6, day06),
+    // Part 2 changes the parse (jokers), so both variants are parsed upfront.
+    Day {
+        number: 7,
+        solve: |report, s, _, _| {
+            let parsed = report.phase(Phase::Parse, || {
+                Ok((
+                    crate::day07::parse_input(s, false)?,
+                    crate::day07::parse_input(s, true)?,
+                ))
+            });
+            if let Some((input, with_jokers)) = parsed {
+                report.part(Phase::Part1, || crate::day07::play(&input));
+                report.part(Phase::Part2, || crate::day07::play(&with_jokers));
+            }
+        },
+    },
+    day!(8, day08),
+    day!(9, day09),
+    day!(10, day10),
+    day!(13, day13),
+    day!(14, day14, traced),
+    day!(15, day15),
+    day!(16, day16, traced),
+    day!(17, day17, traced),
+    day!(18, day18),
+    day!(19, day19),
+    day!(20, day20),
+    day!(21, day21),
+    day!(22, day22),
+    day!(23, day23),
+    day!(24, day24),
+    // Day 25 has no second part.
+    Day {
+        number: 25,
+        solve: |report, s, _, _| {
+            if let Some(input) = report.phase(Phase::Parse, || crate::day25::parse_input(s)) {
+                report.part(Phase::Part1, || crate::day25::part1(&input));
+            }
+        },
+    },
+];
+
+pub fn day(number: u32) -> Option<Day> {
+    DAYS.iter().find(|d| d.number == number).copied()
+}
+
+/// Solves every `(day, input)` pair in parallel, keeping the order of `jobs`.
+/// When counting allocations, days run one at a time so the counts don't mix.
+pub fn run_all(
+    jobs: &[(Day, String)],
+    trace: Option<&TraceSink>,
+    options: &Options,
+) -> Vec<DayReport> {
+    if alloc::ENABLED {
+        return jobs
+            .iter()
+            .map(|(day, input)| day.run_with(input, trace, options))
+            .collect();
+    }
+
+    jobs.par_iter()
+        .map(|(day, input)| day.run_with(input, trace, options))
+        .collect()
+}
+
+/// Solves a day `iterations` times in a row.
+pub fn bench(day: Day, input: &str, iterations: usize) -> Vec<DayReport> {
+    (0..iterations).map(|_| day.run(input, None)).collect()
+}
+
+pub fn format_table(reports: &[DayReport]) -> String {
+    let cell = |report: &DayReport, phase: Phase| match report.get(phase).map(|p| &p.result) {
+        None => "-".to_string(),
+        Some(Ok(answer)) => answer.clone(),
+        Some(Err(Failure::Error(_))) => "ERROR".to_string(),
+        Some(Err(Failure::Panic(_))) => "PANIC".to_string(),
+    };
+
+    let header = ["Day", "Part1", "Part2", "Time"].map(String::from);
+    let mut rows = vec![header];
+    for report in reports {
+        rows.push([
+            report.day.to_string(),
+            cell(report, Phase::Part1),
+            cell(report, Phase::Part2),
+            format!("{:.2?}", report.elapsed),
+        ]);
+    }
+
+    table(&rows)
+}
+
+fn alloc_cells(alloc: Option<AllocStats>) -> [String; 3] {
+    match alloc {
+        Some(stats) => [
+            stats.allocations.to_string(),
+            stats.bytes.to_string(),
+            stats.peak.to_string(),
+        ],
+        None => ["-", "-", "-"].map(String::from),
+    }
+}
+
+/// Allocations of every phase, with the `count-alloc` feature.
+pub fn format_alloc_table(reports: &[DayReport]) -> String {
+    let header = ["Day", "Phase", "Allocs", "Bytes", "Peak"].map(String::from);
+    let mut rows = vec![header];
+    for report in reports {
+        for phase in &report.phases {
+            let [allocations, bytes, peak] = alloc_cells(phase.alloc);
+            rows.push([
+                report.day.to_string(),
+                format!("{:?}", phase.phase),
+                allocations,
+                bytes,
+                peak,
+            ]);
+        }
+    }
+
+    table(&rows)
+}
+
+/// Timings of every phase over the runs of [`bench`], and the allocations of the last run.
+pub fn format_bench(reports: &[DayReport]) -> String {
+    let header = ["Phase", "Min", "Mean", "Max", "Allocs", "Bytes", "Peak"].map(String::from);
+    let mut rows = vec![header];
+
+    for phase in [Phase::Parse, Phase::Part1, Phase::Part2] {
+        let timings = reports
+            .iter()
+            .filter_map(|r| r.get(phase))
+            .map(|p| p.elapsed)
+            .collect::<Vec<_>>();
+        let (Some(min), Some(max)) = (timings.iter().min(), timings.iter().max()) else {
+            continue;
+        };
+        let mean = timings.iter().sum::<Duration>() / timings.len() as u32;
+
+        let last = reports.last().and_then(|r| r.get(phase));
+        let [allocations, bytes, peak] = alloc_cells(last.and_then(|p| p.alloc));
+        rows.push([
+            format!("{:?}", phase),
+            format!("{:.2?}", min),
+            format!("{:.2?}", mean),
+            format!("{:.2?}", max),
+            allocations,
+            bytes,
+            peak,
+        ]);
+    }
+
+    table(&rows)
+}
+
+/// The line by line details behind a day's answers, for the days that have them.
+/// For day 1, the lines where part 1 and part 2 disagree (or every line, with `all`).
+/// For day 2, the games that are impossible with the bag (or every game, with `all`).
+pub fn explain(day: u32, input: &str, all: bool, options: &Options) -> Result<String> {
+    match day {
+        1 => Ok(format_calibration(&day01::parse_input(input)?, all)),
+        2 => {
+            let bag = options.bag.clone().unwrap_or_else(Bag::puzzle);
+            Ok(format_games(&day02::parse_input(input)?, &bag, all))
+        }
+        _ => bail!("day {} has nothing to explain", day),
+    }
+}
+
+fn format_calibration(lines: &[&str], all: bool) -> String {
+    let part1 = day01::calibrate(lines, &day01::DIGIT_MATCHER);
+    let part2 = day01::calibrate(lines, &day01::ENGLISH_MATCHER);
+
+    let value = |c: &Result<LineCalibration, NoDigit>| c.as_ref().ok().map(|c| c.value);
+    let cell = |line: &str, c: &Result<LineCalibration, NoDigit>| match c {
+        Ok(c) => format!(
+            "{} ({} {}..{}, {} {}..{})",
+            c.value,
+            &line[c.first.start..c.first.end],
+            c.first.start,
+            c.first.end,
+            &line[c.last.start..c.last.end],
+            c.last.start,
+            c.last.end,
+        ),
+        Err(_) => "no digit".to_string(),
+    };
+
+    let header = ["Line", "Part1", "Part2", "Text"].map(String::from);
+    let mut rows = vec![header];
+    let mut disagree = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let (p1, p2) = (&part1.lines[i], &part2.lines[i]);
+        let differs = value(p1) != value(p2);
+        disagree += differs as usize;
+        if !differs && !all {
+            continue;
+        }
+
+        // Flag the disagreements among all the lines.
+        let number = match differs && all {
+            true => format!("{}*", i + 1),
+            false => (i + 1).to_string(),
+        };
+        rows.push([number, cell(line, p1), cell(line, p2), line.to_string()]);
+    }
+
+    let mut out = table(&rows);
+    out.push_str(&format!(
+        "Part 1 and part 2 disagree on {} of {} lines\n",
+        disagree,
+        lines.len()
+    ));
+    out
+}
+
+fn format_games(games: &[Game], bag: &Bag, all: bool) -> String {
+    let header = ["Game", "Impossible", "Minimal bag", "Power"].map(String::from);
+    let mut rows = vec![header];
+    let mut impossible = 0;
+    for game in games {
+        let infeasible = game.infeasible(bag);
+        impossible += infeasible.is_some() as usize;
+        if infeasible.is_none() && !all {
+            continue;
+        }
+
+        rows.push([
+            game.id.to_string(),
+            infeasible.map_or("-".to_string(), |i| i.to_string()),
+            game.minimal_bag().to_string(),
+            game.power(bag).to_string(),
+        ]);
+    }
+
+    let mut out = format!("Bag: {}\n", bag);
+    out.push_str(&format!(
+        "Smallest bag for every game: {}\n\n",
+        day02::minimal_bag(games)
+    ));
+    out.push_str(&table(&rows));
+    out.push_str(&format!(
+        "{} of {} games are impossible\n",
+        impossible,
+        games.len()
+    ));
+    out
+}
+
+/// Left aligned columns, with a line under the header.
+fn table<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+
+        if i == 0 {
+            let separator = widths
+                .iter()
+                .map(|w| "-".repeat(*w))
+                .collect::<Vec<_>>()
+                .join("-+-");
+            table.push_str(&separator);
+            table.push('\n');
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_trace_json() {
+        let day14 = textwrap::dedent(
+            "O....#....
+            O.OO#....#
+            .....##...
+            OO.#O....O
+            .O.....O#.
+            O.#..O.#.#
+            ..O..#O..O
+            .......O..
+            #....###..
+            #OO..#....",
+        );
+
+        let buffer = SharedBuffer::default();
+        let sink = TraceSink::new(TraceFormat::Json, buffer.clone());
+        let reports = run_all(
+            &[(day(14).unwrap(), day14)],
+            Some(&sink),
+            &Options::default(),
+        );
+        sink.finish().unwrap();
+        assert!(reports[0].is_ok());
+
+        let out = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events = out
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events[0],
+            serde_json::json!({
+                "day": 14,
+                "phase": "part1",
+                "event": "tilt",
+                "direction": "Up",
+                "load": 136,
+            })
+        );
+        assert_eq!(
+            events.last().unwrap(),
+            &serde_json::json!({
+                "day": 14,
+                "phase": "part2",
+                "event": "cycle_found",
+                "start": 3,
+                "length": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_all() {
+        let day4 = textwrap::dedent(
+            "
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        );
+        let day10 = textwrap::dedent(
+            ".....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....",
+        );
+
+        let jobs = vec![
+            (day(4).unwrap(), day4),
+            (day(10).unwrap(), day10),
+            (day(25).unwrap(), "not a wiring diagram".to_string()),
+        ];
+        let reports = run_all(&jobs, None, &Options::default());
+
+        assert!(reports[0].is_ok());
+        assert_eq!(
+            reports[0].get(Phase::Part1).unwrap().result,
+            Ok("13".into())
+        );
+        assert_eq!(
+            reports[0].get(Phase::Part2).unwrap().result,
+            Ok("30".into())
+        );
+
+        // Day 10 part 2 is still a `todo!()`, part 1 should be unaffected.
+        assert_eq!(reports[1].get(Phase::Part1).unwrap().result, Ok("4".into()));
+        assert!(matches!(
+            reports[1].get(Phase::Part2).unwrap().result,
+            Err(Failure::Panic(_))
+        ));
+
+        assert!(matches!(
+            reports[2].get(Phase::Parse).unwrap().result,
+            Err(Failure::Error(_))
+        ));
+        assert!(reports[2].get(Phase::Part1).is_none());
+
+        let table = format_table(&reports);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].starts_with("4   | 13    | 30    |"));
+        assert!(lines[3].starts_with("10  | 4     | PANIC |"));
+        assert!(lines[4].starts_with("25  | -     | -     |"));
+    }
+
+    #[test]
+    fn test_explain_day1() {
+        let input = "1abc2\nxtwone3four\nabc\n7pqrstsixteen\n";
+
+        let explained = explain(1, input, false, &Options::default()).unwrap();
+        let lines = explained.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[2],
+            "2    | 33 (3 6..7, 3 6..7) | 24 (two 1..4, four 7..11) | xtwone3four"
+        );
+        assert!(lines[3].starts_with("4    | 77 (7 0..1, 7 0..1) | 76 "));
+        assert_eq!(lines[4], "Part 1 and part 2 disagree on 2 of 4 lines");
+        assert!(!explained.contains("1abc2"));
+        assert!(!explained.contains("no digit"));
+
+        let explained = explain(1, input, true, &Options::default()).unwrap();
+        let lines = explained.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[2].starts_with("1    | 12 "));
+        assert!(lines[3].starts_with("2*   | 33 "));
+        assert!(lines[4].starts_with("3    | no digit "));
+
+        assert!(explain(3, input, false, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn test_explain_day2() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+";
+
+        let explained = explain(2, input, false, &Options::default()).unwrap();
+        let lines = explained.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Bag: red=12,green=13,blue=14");
+        assert_eq!(
+            lines[1],
+            "Smallest bag for every game: blue=6,green=13,red=20"
+        );
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[5],
+            "3    | showing 1 has 20 red, the bag has 12 | blue=6,green=13,red=20 | 1560"
+        );
+        assert_eq!(lines[6], "1 of 3 games are impossible");
+
+        let options = Options {
+            bag: Some("red=20,green=2".parse().unwrap()),
+        };
+        let explained = explain(2, input, true, &options).unwrap();
+        let lines = explained.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 9);
+        assert!(lines[5].starts_with("1    | showing 1 has 3 blue, the bag has 0 |"));
+        assert!(lines[5].ends_with("| 8"));
+        assert!(lines[6].starts_with("2    | showing 1 has 1 blue, the bag has 0 |"));
+        assert_eq!(lines[8], "3 of 3 games are impossible");
+    }
+}
@@ -0,0 +1,81 @@
+//! Structured per-step events from the simulations, for following a solution step by step.
+//!
+//! Unlike `log::debug!`, which `release_max_level_info` compiles out of release builds,
+//! events go to whatever [`Tracer`] the caller passes in, so they're there in any build.
+
+use serde::Serialize;
+
+use crate::Direction;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Day 5: a single seed went through all the mapping layers.
+    SeedLocation { seed: i64, location: i64 },
+    /// Day 5: a seed range is about to go through the mapping layers.
+    SeedRange { start: i64, end: i64 },
+    /// Day 5: a (half-open) range came out of mapping layer `layer`.
+    RangeMapped { layer: usize, start: i64, end: i64 },
+    /// Day 14: the platform was tilted and all the rocks rolled.
+    Tilt { direction: Direction, load: i64 },
+    /// Day 14: spin cycle `cycle` finished.
+    SpinCycle { cycle: usize, load: i64 },
+    /// Day 14: the platform after `start + length` cycles is the same as after `start`.
+    CycleFound { start: usize, length: usize },
+    /// Day 16: a beam entered a tile.
+    Beam {
+        position: (i64, i64),
+        direction: Direction,
+    },
+    /// Day 16: all the beams from a starting tile died out or looped.
+    Energized {
+        start: (i64, i64),
+        direction: Direction,
+        tiles: usize,
+    },
+    /// Day 17: the moves out of a crucible state were expanded.
+    Expand {
+        position: (i64, i64),
+        direction: Direction,
+        straight: i32,
+        moves: usize,
+    },
+    /// Day 17: a crucible state along the best path.
+    PathStep {
+        position: (i64, i64),
+        direction: Direction,
+        straight: i32,
+    },
+    /// Day 17: the best path when starting off in `direction`.
+    PathFound {
+        direction: Direction,
+        heat_loss: i64,
+    },
+}
+
+pub trait Tracer {
+    fn event(&mut self, event: Event);
+
+    /// Whether events are wanted at all. Callers check this before building an event
+    /// that costs something to compute, like a load, and before tracing in a hot loop.
+    fn enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Drops all events, for the untraced `part1` / `part2`.
+pub struct NoTrace;
+
+impl Tracer for NoTrace {
+    fn event(&mut self, _event: Event) {}
+
+    fn enabled(&self) -> bool {
+        false
+    }
+}
+
+impl Tracer for Vec<Event> {
+    fn event(&mut self, event: Event) {
+        self.push(event);
+    }
+}